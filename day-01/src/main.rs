@@ -1,53 +1,53 @@
-use utils::string::read;
+use utils::{
+    error::{solve, ParseError},
+    string::{parse_lines, read},
+};
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 1 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 1 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> u32 {
-    read(file_name)
-        .iter()
-        .map(|line| {
-            line.chars()
-                .filter_map(|c| c.to_digit(10))
-                .collect::<Vec<u32>>()
-        })
-        .map(|vec| {
-            10 * vec
-                .first()
-                .expect("Every line MUST contain at least one digit")
-                + vec
-                    .last()
-                    .expect("Every line MUST contain at least one digit")
-        })
-        .sum::<u32>()
+fn part_one(file_name: &str) -> Result<u32, ParseError> {
+    let values = parse_lines(&read(file_name), |line| {
+        calibration_value(line, line.chars().filter_map(|c| c.to_digit(10)).collect())
+    })?;
+    Ok(values.iter().sum())
 }
 
-fn part_two(file_name: &str) -> u32 {
-    read(file_name)
-        .iter()
-        .map(|line| {
-            line.to_string()
-                .replace("zero", "zero0zero")
-                .replace("one", "one1one")
-                .replace("two", "two2two")
-                .replace("three", "three3three")
-                .replace("four", "four4four")
-                .replace("five", "five5five")
-                .replace("six", "six6six")
-                .replace("seven", "seven7seven")
-                .replace("eight", "eight8eight")
-                .replace("nine", "nine9nine")
-                .chars()
-                .filter_map(|c| c.to_digit(10))
-                .collect::<Vec<u32>>()
-        })
-        .map(|vec| 10 * vec.first().unwrap() + vec.last().unwrap())
-        .sum::<u32>()
+fn part_two(file_name: &str) -> Result<u32, ParseError> {
+    let values = parse_lines(&read(file_name), |line| {
+        let digits = line
+            .to_string()
+            .replace("zero", "zero0zero")
+            .replace("one", "one1one")
+            .replace("two", "two2two")
+            .replace("three", "three3three")
+            .replace("four", "four4four")
+            .replace("five", "five5five")
+            .replace("six", "six6six")
+            .replace("seven", "seven7seven")
+            .replace("eight", "eight8eight")
+            .replace("nine", "nine9nine")
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .collect();
+        calibration_value(line, digits)
+    })?;
+    Ok(values.iter().sum())
+}
+
+/**
+ * The first and last digit of the line form a two-digit number
+ */
+fn calibration_value(line: &str, digits: Vec<u32>) -> Result<u32, ParseError> {
+    match (digits.first(), digits.last()) {
+        (Some(first), Some(last)) => Ok(10 * first + last),
+        _ => Err(ParseError::new(1, 1, line, "at least one digit")),
+    }
 }
 
 #[cfg(test)]
@@ -56,11 +56,11 @@ mod test {
 
     #[test]
     fn part_one_test() {
-        assert_eq!(142, part_one("data/example.txt"))
+        assert_eq!(Ok(142), part_one("data/example.txt"))
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(281, part_two("data/example_2.txt"))
+        assert_eq!(Ok(281), part_two("data/example_2.txt"))
     }
 }
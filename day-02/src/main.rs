@@ -1,6 +1,8 @@
-use regex::Regex;
-use std::u32;
-use utils::string::read;
+use utils::{
+    error::{solve, ParseError},
+    parse::{alt, delimited, integer, map, pair, parse_all, separated, tag, trim},
+    string::{parse_lines, read},
+};
 
 #[derive(Debug)]
 enum Color {
@@ -26,17 +28,18 @@ static GREEN_THRESHOLD: u32 = 13;
 static BLUE_THRESHOLD: u32 = 14;
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 2 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 2 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> u32 {
-    read(file_name)
+fn part_one(file_name: &str) -> Result<u32, ParseError> {
+    let games = parse_lines(&read(file_name), parse_game)?;
+
+    Ok(games
         .iter()
-        .map(|line| parse_game(line))
         .filter(|game| {
             game.rounds.iter().all(|round| {
                 round.iter().all(|cube| match cube {
@@ -56,13 +59,14 @@ fn part_one(file_name: &str) -> u32 {
             })
         })
         .map(|game| game.id)
-        .sum()
+        .sum())
 }
 
-fn part_two(file_name: &str) -> u32 {
-    read(file_name)
+fn part_two(file_name: &str) -> Result<u32, ParseError> {
+    let games = parse_lines(&read(file_name), parse_game)?;
+
+    Ok(games
         .iter()
-        .map(|line| parse_game(line))
         .map(|game| {
             let mut min_red: u32 = 0;
             let mut min_green: u32 = 0;
@@ -99,64 +103,55 @@ fn part_two(file_name: &str) -> u32 {
 
             min_red * min_green * min_blue
         })
-        .sum()
+        .sum())
 }
 
-fn parse_game(line: &str) -> Game {
-    let re = Regex::new(r"^Game ([0-9]+):").unwrap();
-    let Some(caps) = re.captures(line) else {
-        panic!("Failed to match game_id in line '{line:?}'")
-    };
-
-    let game_id = caps.get(1).unwrap().as_str().parse::<u32>().unwrap();
-
-    let rounds: Vec<Vec<Cube>> = re
-        .replace(line, "")
-        .split(";")
-        .map(|round| {
-            round
-                .trim()
-                .split(",")
-                .map(|cube| {
-                    let cubes: Vec<&str> = cube.split_ascii_whitespace().collect();
-
-                    match &cubes[..] {
-                        [count, "red"] => Cube {
-                            color: Color::Red,
-                            count: count.parse::<u32>().unwrap(),
-                        },
-                        [count, "blue"] => Cube {
-                            color: Color::Blue,
-                            count: count.parse::<u32>().unwrap(),
-                        },
-                        [count, "green"] => Cube {
-                            color: Color::Green,
-                            count: count.parse::<u32>().unwrap(),
-                        },
-                        _ => panic!("Failed to match any colors. How?"),
-                    }
-                })
-                .collect()
-        })
-        .collect();
-
-    return Game {
-        id: game_id,
-        rounds: rounds,
-    };
+fn parse_game(line: &str) -> Result<Game, ParseError> {
+    let color = alt((
+        map(tag("red"), |_| Color::Red),
+        map(tag("green"), |_| Color::Green),
+        map(tag("blue"), |_| Color::Blue),
+    ));
+    let cube = map(pair(trim(integer()), color), |(count, color)| Cube {
+        color,
+        count,
+    });
+    let round = separated(trim(cube), tag(","));
+    let game = pair(
+        delimited(tag("Game "), integer(), tag(":")),
+        separated(round, tag(";")),
+    );
+
+    let (id, rounds) = parse_all(game, line)?;
+    Ok(Game { id, rounds })
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{part_one, part_two};
+    use utils::error::ParseError;
+
+    use crate::{parse_game, part_one, part_two};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(8, part_one("data/example.txt"))
+        assert_eq!(Ok(8), part_one("data/example.txt"))
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(2286, part_two("data/example.txt"))
+        assert_eq!(Ok(2286), part_two("data/example.txt"))
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                20,
+                "Game 3: 8 green, 6 purple",
+                "'red' or 'green' or 'blue'"
+            )),
+            parse_game("Game 3: 8 green, 6 purple").map(|game| game.id)
+        );
     }
 }
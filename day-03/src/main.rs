@@ -1,11 +1,10 @@
-use std::vec;
-
 use itertools::Itertools;
 use regex::Regex;
-use utils::string::read;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point(i32, i32);
+use utils::{
+    error::{solve, ParseError},
+    point::Point,
+    string::read,
+};
 
 #[derive(Debug)]
 struct Part {
@@ -20,19 +19,19 @@ struct Symbol {
 }
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 3 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 3 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> u32 {
+fn part_one(file_name: &str) -> Result<u32, ParseError> {
     let lines = read(file_name);
-    let (symbols, parts) = parse_schematic(lines);
+    let (symbols, parts) = parse_schematic(lines)?;
     let symbol_points: Vec<Point> = symbols.iter().map(|s| s.idx).collect();
 
-    parts
+    Ok(parts
         .iter()
         .filter_map(|part| {
             if neighbours(&part.indices)
@@ -44,18 +43,18 @@ fn part_one(file_name: &str) -> u32 {
                 None
             }
         })
-        .sum::<u32>()
+        .sum::<u32>())
 }
 
-fn part_two(file_name: &str) -> u32 {
+fn part_two(file_name: &str) -> Result<u32, ParseError> {
     let lines = read(file_name);
-    let (symbols, parts) = parse_schematic(lines);
+    let (symbols, parts) = parse_schematic(lines)?;
 
-    symbols
+    Ok(symbols
         .iter()
         .filter(|s| s.char == '*')
         .filter_map(|s| {
-            let neighbours = neighbour(&s.idx);
+            let neighbours = s.idx.neighbours8().collect::<Vec<_>>();
 
             let viable_parts: Vec<&Part> = parts
                 .iter()
@@ -68,74 +67,77 @@ fn part_two(file_name: &str) -> u32 {
                 None
             }
         })
-        .sum()
+        .sum())
 }
 
-fn parse_schematic(lines: Vec<String>) -> (Vec<Symbol>, Vec<Part>) {
+fn parse_schematic(lines: Vec<String>) -> Result<(Vec<Symbol>, Vec<Part>), ParseError> {
     let number_matcher = Regex::new(r"\d+").unwrap();
 
     let mut parts: Vec<Part> = vec![];
     let mut symbols: Vec<Symbol> = vec![];
 
-    lines.iter().enumerate().for_each(|(idy, line)| {
-        number_matcher.find_iter(line).for_each(|caps| {
-            let part_no = caps
-                .as_str()
-                .parse::<u32>()
-                .expect("Failed to parse part number!");
+    for (idy, line) in lines.iter().enumerate() {
+        for caps in number_matcher.find_iter(line) {
+            let part_no = caps.as_str().parse::<u32>().map_err(|_| {
+                ParseError::new(
+                    idy + 1,
+                    line[..caps.start()].chars().count() + 1,
+                    line,
+                    "a part number fitting in u32",
+                )
+            })?;
             let indices = (caps.start()..caps.end())
-                .map(|idx| Point(idx as i32, idy as i32))
+                .map(|idx| Point::from((idx, idy)))
                 .collect();
 
             parts.push(Part { part_no, indices });
-        });
+        }
 
         line.char_indices().for_each(|(idx, char)| {
             if !char.is_alphanumeric() && char != '.' {
                 symbols.push(Symbol {
-                    idx: Point(idx as i32, idy as i32),
+                    idx: Point::from((idx, idy)),
                     char,
                 });
             }
         });
-    });
+    }
 
-    (symbols, parts)
+    Ok((symbols, parts))
 }
 
-fn neighbours(indices: &Vec<Point>) -> Vec<Point> {
+fn neighbours(indices: &[Point]) -> Vec<Point> {
     indices
         .iter()
-        .flat_map(|idx| neighbour(idx))
+        .flat_map(|idx| idx.neighbours8())
         .unique()
         .collect()
 }
 
-fn neighbour(point: &Point) -> Vec<Point> {
-    return vec![
-        Point(point.0 - 1, point.1 - 1),
-        Point(point.0 - 1, point.1),
-        Point(point.0 - 1, point.1 + 1),
-        Point(point.0, point.1 - 1),
-        Point(point.0, point.1),
-        Point(point.0, point.1 + 1),
-        Point(point.0 + 1, point.1 - 1),
-        Point(point.0 + 1, point.1),
-        Point(point.0 + 1, point.1 + 1),
-    ];
-}
-
 #[cfg(test)]
 mod test {
-    use crate::{part_one, part_two};
+    use utils::error::ParseError;
+
+    use crate::{parse_schematic, part_one, part_two};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(4361, part_one("data/example.txt"))
+        assert_eq!(Ok(4361), part_one("data/example.txt"))
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(467835, part_two("data/example.txt"))
+        assert_eq!(Ok(467835), part_two("data/example.txt"))
+    }
+
+    #[test]
+    fn parse_error_test() {
+        // columns count characters, not bytes
+        let error =
+            parse_schematic(vec!["12.".to_owned(), "€..99999999999".to_owned()]).unwrap_err();
+        assert_eq!(
+            ParseError::new(2, 4, "€..99999999999", "a part number fitting in u32"),
+            error
+        );
     }
 }
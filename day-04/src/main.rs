@@ -1,19 +1,25 @@
 use std::collections::HashMap;
 
-use utils::string::read;
+use utils::{
+    error::{solve, ParseError},
+    scan,
+    scan::Located,
+    string::{parse_lines, read, try_uints},
+};
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 4 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 4 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> u32 {
-    read(file_name)
-        .iter()
-        .map(|line| parse_cards(line))
+fn part_one(file_name: &str) -> Result<u32, ParseError> {
+    let cards = parse_lines(&read(file_name), parse_cards)?;
+
+    Ok(cards
+        .into_iter()
         .filter_map(|matches| {
             if matches > 0 {
                 Some(u32::pow(2, (matches - 1) as u32))
@@ -21,15 +27,15 @@ fn part_one(file_name: &str) -> u32 {
                 None
             }
         })
-        .sum::<u32>()
+        .sum::<u32>())
 }
 
-fn part_two(file_name: &str) -> u32 {
+fn part_two(file_name: &str) -> Result<u32, ParseError> {
+    let cards = parse_lines(&read(file_name), parse_cards)?;
     let mut card_count = HashMap::new();
 
-    read(file_name)
-        .iter()
-        .map(|line| parse_cards(line))
+    Ok(cards
+        .into_iter()
         .enumerate()
         .fold(0, |acc, (idx, matches)| {
             let id = idx + 1;
@@ -41,42 +47,73 @@ fn part_two(file_name: &str) -> u32 {
                 *card_count.entry(next_id).or_insert(1) += current_card_count);
 
             acc + current_card_count
-        })
+        }))
 }
 
-fn parse_cards(line: &str) -> usize {
-    let mut split_line = line.split(":");
-    let _ = split_line.next(); // this would contain the card id
-
-    let nums = split_line.next().unwrap();
+fn parse_cards(line: &str) -> Result<usize, ParseError> {
+    let (_id, target_nums, owned_nums) =
+        scan!(line, "Card {}: {} | {}" => usize, Located<String>, Located<String>)?;
+    let target_nums = numbers(line, &target_nums)?;
+    let owned_nums = numbers(line, &owned_nums)?;
 
-    let mut split_nums = nums.splitn(2, "|");
-    let target_nums = parse_nums(split_nums.next().unwrap());
-    let owned_nums = parse_nums(split_nums.next().unwrap());
-
-    target_nums
+    Ok(target_nums
         .iter()
         .filter(|wn| owned_nums.contains(wn))
-        .count()
+        .count())
 }
 
-fn parse_nums(line: &str) -> Vec<u32> {
-    line.split_whitespace()
-        .map(|n| n.parse::<u32>().unwrap())
-        .collect()
+/**
+ * The numbers in a field scanned from the line, with errors pointing into the whole line
+ */
+fn numbers(line: &str, field: &Located<String>) -> Result<Vec<u32>, ParseError> {
+    try_uints(&field.value)
+        .map_err(|e| ParseError::new(1, field.column + e.column - 1, line.trim_end(), e.expected))
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{part_one, part_two};
+    use utils::error::ParseError;
+
+    use crate::{parse_cards, part_one, part_two};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(13, part_one("data/example.txt"))
+        assert_eq!(Ok(13), part_one("data/example.txt"))
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(30, part_two("data/example.txt"))
+        assert_eq!(Ok(30), part_two("data/example.txt"))
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            Err(ParseError::new(1, 1, "Crd 1: 1 2 | 3", "'Card '")),
+            parse_cards("Crd 1: 1 2 | 3")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 6, "Card x: 1 2 | 3", "a usize")),
+            parse_cards("Card x: 1 2 | 3")
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                18,
+                "Card 1: 1 2 |  3 99999999999",
+                "a number fitting in u32"
+            )),
+            parse_cards("Card 1: 1 2 |  3 99999999999")
+        );
+        // the card id and the numbers share their text
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                23,
+                "Card 99999999999: 1 | 99999999999",
+                "a number fitting in u32"
+            )),
+            parse_cards("Card 99999999999: 1 | 99999999999")
+        );
     }
 }
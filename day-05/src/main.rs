@@ -1,31 +1,38 @@
-use utils::string::read_string;
+use utils::{
+    error::{solve, ParseError},
+    parse::{integer, many1, map, pair, parse_all, preceded, tag, take_while1, trim},
+    string::{numbered_sections, read_string},
+};
+
+type Almanac = (Vec<usize>, Vec<Vec<[usize; 3]>>);
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 5 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 5 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> usize {
+fn part_one(file_name: &str) -> Result<usize, ParseError> {
     let input = read_string(file_name);
-    let (seeds, maps) = parse(&input);
+    let (seeds, maps) = parse(&input)?;
 
-    seeds
+    Ok(seeds
         .iter()
         .map(|&s| maps.iter().fold(s, |s, maps| lookup_seed(&s, maps)))
         .min()
-        .unwrap()
+        .unwrap())
 }
 
-fn part_two(file_name: &str) -> usize {
+fn part_two(file_name: &str) -> Result<usize, ParseError> {
     let input = read_string(file_name);
-    let (seeds, maps) = parse(&input);
+    let (seeds, maps) = parse(&input)?;
 
-    let seed_ranges: Vec<[usize; 2]> = seeds.chunks(2).map(|ch| [ch[0], ch[0] + ch[1]]).collect();
+    let seed_ranges = seed_ranges(&input, &seeds)?;
 
-    maps.iter()
+    Ok(maps
+        .iter()
         .fold(seed_ranges, |seed_ranges, map| {
             seed_ranges
                 .iter()
@@ -65,64 +72,109 @@ fn part_two(file_name: &str) -> usize {
         .iter()
         .map(|r| r[0])
         .min()
-        .unwrap()
+        .unwrap())
 }
 
 /**
  * Find the first entry whose range matches the seed, and determin its next value.
  * If none of the ranges matches, we return the original seed value.
  */
-fn lookup_seed(seed: &usize, maps: &Vec<[usize; 3]>) -> usize {
+fn lookup_seed(seed: &usize, maps: &[[usize; 3]]) -> usize {
     maps.iter()
         .find(|&[_, src, len]| src <= seed && src + len >= *seed)
-        .and_then(|&[dst, src, _]| Some(seed - src + dst))
-        .unwrap_or_else(|| *seed)
+        .map(|&[dst, src, _]| seed - src + dst)
+        .unwrap_or(*seed)
 }
 
-fn parse(input: &str) -> (Vec<usize>, Vec<Vec<[usize; 3]>>) {
-    let sections = input.trim().split("\n\n").collect::<Vec<_>>();
-    let seeds = parse_seeds(sections[0]);
-    let maps = sections[1..].iter().map(|s| parse_maps(s)).collect();
-
-    (seeds, maps)
+/**
+ * The seeds read as pairs of range start and length, as [start, end) ranges
+ */
+fn seed_ranges(input: &str, seeds: &[usize]) -> Result<Vec<[usize; 2]>, ParseError> {
+    if seeds.len().is_multiple_of(2) {
+        return Ok(seeds.chunks(2).map(|ch| [ch[0], ch[0] + ch[1]]).collect());
+    }
+    let (first_line, line) = numbered_sections(input).next().unwrap_or_default();
+    Err(ParseError::new(
+        1,
+        line.chars().count() + 1,
+        line,
+        "a length for the last seed range",
+    )
+    .offset_lines(first_line))
 }
 
-fn parse_seeds(input: &str) -> Vec<usize> {
-    let parts = input.trim().split(": ").collect::<Vec<_>>();
-    parts[1]
-        .split(" ")
-        .map(|s| s.parse::<usize>().unwrap())
-        .collect::<Vec<_>>()
+fn parse(input: &str) -> Result<Almanac, ParseError> {
+    let mut sections = numbered_sections(input);
+    let (first_line, seeds) = sections
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "", "'seeds:'"))?;
+    let seeds = parse_all(preceded(tag("seeds:"), many1(trim(integer()))), &seeds)
+        .map_err(|e| e.offset_lines(first_line))?;
+
+    let maps = sections
+        .map(|(first_line, section)| parse_maps(&section).map_err(|e| e.offset_lines(first_line)))
+        .collect::<Result<_, _>>()?;
+
+    Ok((seeds, maps))
 }
 
-fn parse_maps(input: &str) -> Vec<[usize; 3]> {
-    let lines = input.trim().lines().skip(1);
+/**
+ * A map section: a header like "seed-to-soil map:" and rows of destination, source and length
+ */
+fn parse_maps(section: &str) -> Result<Vec<[usize; 3]>, ParseError> {
+    let mut lines = section.lines();
+    let header = lines.next().unwrap_or("");
+    parse_all(
+        pair(take_while1(|c| c != ' ', "a map name"), tag(" map:")),
+        header,
+    )?;
+
+    let number = || trim(integer::<usize>());
+    let row = map(
+        pair(number(), pair(number(), number())),
+        |(dst, (src, len))| [dst, src, len],
+    );
 
     lines
-        .map(|line| {
-            let parts = line
-                .trim()
-                .to_string()
-                .split_whitespace()
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
-
-            [parts[0], parts[1], parts[2]]
-        })
+        .enumerate()
+        .map(|(index, line)| parse_all(&row, line).map_err(|e| e.offset_lines(index + 1)))
         .collect()
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{part_one, part_two};
+    use utils::error::ParseError;
+
+    use crate::{parse_maps, part_one, part_two};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(35, part_one("data/example.txt"))
+        assert_eq!(Ok(35), part_one("data/example.txt"))
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(46, part_two("data/example.txt"))
+        assert_eq!(Ok(46), part_two("data/example.txt"))
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            Err(ParseError::new(20, 8, "18 25 7O", "end of input")),
+            part_one("data/malformed.txt")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 14, "seed-to-soil:", "' map:'")),
+            parse_maps("seed-to-soil:\n50 98 2")
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                3,
+                4,
+                "52 -50 48",
+                "an integer fitting in usize"
+            )),
+            parse_maps("seed-to-soil map:\n50 98 2\n52 -50 48")
+        );
     }
 }
@@ -1,81 +1,123 @@
-use utils::string::read;
+use utils::{
+    error::{solve, ParseError},
+    num::count_integer_solutions,
+    parse::{integer, many1, parse_all, preceded, tag, take_while1, trim, Parser},
+    string::read,
+};
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 6 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 6 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> u32 {
+fn part_one(file_name: &str) -> Result<u32, ParseError> {
     let input = read(file_name);
-    let race = parse_race(input);
-
-    race.iter()
-        .map(|&[time, record]| {
-            (0..time)
-                .map(|speed| speed * (time - speed))
-                .filter(|&distance| distance > record)
-                .count()
-        })
-        .fold(1, |acc, result| acc * result as u32)
+    let race = parse_race(&input)?;
+
+    Ok(race
+        .iter()
+        .map(|&[time, record]| winning_charges(time.into(), record.into()) as u32)
+        .product())
 }
 
-fn part_two(file_name: &str) -> u64 {
+fn part_two(file_name: &str) -> Result<u128, ParseError> {
     let input = read(file_name);
-    let (time, record) = parse_single_race(input);
-
-    let cutoff = (0..time)
-        .take_while(|charge| charge * (time - charge) <= record)
-        .last()
-        .unwrap();
+    let (time, record) = parse_single_race(&input)?;
 
-    time - (2 * (cutoff + 1)) + 1
+    Ok(winning_charges(time, record))
 }
 
-fn parse_single_race(input: Vec<String>) -> (u64, u64) {
-    let time_parts = input[0].split(":").collect::<Vec<_>>();
-    let time = time_parts[1].replace(" ", "").parse::<u64>().unwrap();
-
-    let record_parts = input[1].split(":").collect::<Vec<_>>();
-    let record = record_parts[1].replace(" ", "").parse::<u64>().unwrap();
+/**
+ * Charging for x ms covers x * (time - x) mm, so the winning charges are the integer solutions of
+ * -x^2 + time * x - record > 0.
+ */
+fn winning_charges(time: i128, record: i128) -> u128 {
+    count_integer_solutions(-1, time, -record)
+        .expect("a downward opening parabola has finitely many solutions")
+}
 
-    (time, record)
+/**
+ * The values after the label on the given line
+ */
+fn parse_line<'a, T>(
+    input: &'a [String],
+    index: usize,
+    label: &'static str,
+    value: impl Parser<'a, T>,
+) -> Result<Vec<T>, ParseError> {
+    let line = input.get(index).map_or("", String::as_str);
+    parse_all(preceded(tag(label), many1(trim(value))), line).map_err(|e| e.offset_lines(index))
 }
 
-fn parse_race(input: Vec<String>) -> Vec<[u32; 2]> {
-    let time_parts = input[0].split(":").collect::<Vec<_>>();
-    let times = parse_nums(time_parts[1]);
+fn parse_single_race(input: &[String]) -> Result<(i128, i128), ParseError> {
+    // the spaces between the numbers are bad kerning, so they form a single number
+    let number = |index: usize, label: &'static str| {
+        let digits = take_while1(|c| c.is_ascii_digit(), "a number");
+        parse_line(input, index, label, digits)?
+            .concat()
+            .parse::<i128>()
+            .map_err(|_| {
+                ParseError::new(
+                    index + 1,
+                    label.len() + 1,
+                    &input[index],
+                    "a number fitting in i128",
+                )
+            })
+    };
+
+    Ok((number(0, "Time:")?, number(1, "Distance:")?))
+}
 
-    let record_parts = input[1].split(":").collect::<Vec<_>>();
-    let records = parse_nums(record_parts[1]);
+fn parse_race(input: &[String]) -> Result<Vec<[u32; 2]>, ParseError> {
+    let numbers = |index: usize, label: &'static str| parse_line(input, index, label, integer());
+    let times = numbers(0, "Time:")?;
+    let records = numbers(1, "Distance:")?;
+
+    if times.len() != records.len() {
+        return Err(ParseError::new(
+            2,
+            input[1].chars().count() + 1,
+            &input[1],
+            format!("a record for each of the {} races", times.len()),
+        ));
+    }
 
-    times
+    Ok(times
         .iter()
-        .enumerate()
-        .map(|(idx, t)| [*t, records[idx]])
-        .collect()
-}
-
-fn parse_nums(input: &str) -> Vec<u32> {
-    input
-        .split_whitespace()
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect::<Vec<_>>()
+        .zip(records)
+        .map(|(&time, record)| [time, record])
+        .collect())
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{part_one, part_two};
+    use crate::{part_one, part_two, winning_charges};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(288, part_one("data/example.txt"))
+        assert_eq!(Ok(288), part_one("data/example.txt"))
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(71503, part_two("data/example.txt"))
+        assert_eq!(Ok(71503), part_two("data/example.txt"))
+    }
+
+    #[test]
+    fn long_race_test() {
+        let time = u64::MAX as i128;
+        // the best charges are the two closest to half the time
+        let best = time / 2 * (time / 2 + 1);
+
+        assert_eq!(time as u128 - 1, winning_charges(time, 0));
+        assert_eq!(2, winning_charges(time, best - 1));
+        assert_eq!(0, winning_charges(time, best));
+        assert_eq!(0, winning_charges(time, i128::MAX));
+        assert_eq!(i128::MAX as u128 - 1, winning_charges(i128::MAX, 0));
+        assert_eq!(i128::MAX as u128 - 3, winning_charges(i128::MAX, i128::MAX));
     }
 }
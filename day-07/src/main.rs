@@ -1,6 +1,11 @@
 use std::{cmp::Ordering, collections::HashMap};
 
-use utils::string::read;
+use utils::{
+    error::{solve, ParseError},
+    scan,
+    scan::Located,
+    string::{parse_lines, read},
+};
 
 #[derive(Debug, PartialEq, Eq)]
 struct Cards {
@@ -30,12 +35,12 @@ fn card_cmp(cards: &[u32; 5], other: &[u32; 5]) -> Ordering {
         .zip(other.iter())
         .find_map(|(card, other)| {
             if card != other {
-                Some(card.cmp(&other))
+                Some(card.cmp(other))
             } else {
                 None
             }
         })
-        .unwrap_or_else(|| Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
 }
 
 impl Ord for Hand {
@@ -92,64 +97,64 @@ impl PartialOrd for Hand {
 }
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 7 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 7 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> u32 {
+fn part_one(file_name: &str) -> Result<u32, ParseError> {
     play_camel_cards(file_name, false)
 }
 
-fn part_two(file_name: &str) -> u32 {
+fn part_two(file_name: &str) -> Result<u32, ParseError> {
     play_camel_cards(file_name, true)
 }
 
-fn play_camel_cards(file_name: &str, with_joker: bool) -> u32 {
-    let mut rounds = read(file_name)
-        .iter()
-        .map(|i| parse_round(i, with_joker))
-        .collect::<Vec<_>>();
+fn play_camel_cards(file_name: &str, with_joker: bool) -> Result<u32, ParseError> {
+    let mut rounds = parse_lines(&read(file_name), |line| parse_round(line, with_joker))?;
 
     rounds.sort_by(|round, other| round.hand.cmp(&other.hand));
 
-    rounds
+    Ok(rounds
         .iter()
         .map(|hand| hand.bid)
         .enumerate()
-        .fold(0, |acc, (idx, bid)| acc + ((idx as u32 + 1) * bid))
+        .fold(0, |acc, (idx, bid)| acc + ((idx as u32 + 1) * bid)))
 }
 
-fn parse_round(input: &str, with_joker: bool) -> Round {
-    let parts = input.split_whitespace().collect::<Vec<_>>();
-    let cards: [u32; 5] = parts[0]
+fn parse_round(input: &str, with_joker: bool) -> Result<Round, ParseError> {
+    let (Located { column, value }, bid) = scan!(input, "{} {}" => Located<String>, u32)?;
+
+    let cards = value
         .chars()
-        .take(5)
-        .map(|c| map_card_value(&c, with_joker))
-        .collect::<Vec<_>>()
+        .enumerate()
+        .map(|(idx, c)| {
+            map_card_value(&c, with_joker).ok_or_else(|| {
+                ParseError::new(1, column + idx, input, "a card (A, K, Q, J, T or 2-9)")
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let cards: [u32; 5] = cards
         .try_into()
-        .unwrap();
+        .map_err(|_| ParseError::new(1, column, input, "a hand of 5 cards"))?;
 
-    let bid = parts[1].parse::<u32>().unwrap();
     let hand = parse_hand(cards, with_joker);
 
-    Round { hand, bid }
+    Ok(Round { hand, bid })
 }
 
-fn map_card_value(card: &char, with_joker: bool) -> u32 {
+fn map_card_value(card: &char, with_joker: bool) -> Option<u32> {
     let j_value = if with_joker { 1 } else { 11 };
 
     match card {
-        'A' => 14,
-        'K' => 13,
-        'Q' => 12,
-        'J' => j_value,
-        'T' => 10,
-        _ => card
-            .to_digit(10)
-            .unwrap_or_else(|| panic!("{} is not a valid card!", card)),
+        'A' => Some(14),
+        'K' => Some(13),
+        'Q' => Some(12),
+        'J' => Some(j_value),
+        'T' => Some(10),
+        _ => card.to_digit(10).filter(|&value| value >= 2),
     }
 }
 
@@ -167,10 +172,7 @@ fn parse_hand(cards: [u32; 5], with_joker: bool) -> Hand {
             .max_by_key(|(_, v)| *v)
             .map(|(k, _)| k)
         {
-            let joker_count = *card_counts
-                .get(&1 /* joker */)
-                .map(|count| count)
-                .unwrap_or_else(|| &0);
+            let joker_count = *card_counts.get(&1 /* joker */).unwrap_or(&0);
 
             *card_counts.entry(*max).or_insert(0) += joker_count;
             card_counts.remove(&1 /* joker */);
@@ -198,15 +200,42 @@ fn parse_hand(cards: [u32; 5], with_joker: bool) -> Hand {
 
 #[cfg(test)]
 mod test {
-    use crate::{part_one, part_two};
+    use utils::error::ParseError;
+
+    use crate::{map_card_value, parse_round, part_one, part_two};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(6440, part_one("data/example.txt"));
+        assert_eq!(Ok(6440), part_one("data/example.txt"));
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(5905, part_two("data/example.txt"));
+        assert_eq!(Ok(5905), part_two("data/example.txt"));
+    }
+
+    #[test]
+    fn map_card_value_test() {
+        assert_eq!(Some(11), map_card_value(&'J', false));
+        assert_eq!(Some(1), map_card_value(&'J', true));
+        assert_eq!(Some(2), map_card_value(&'2', false));
+        assert_eq!(None, map_card_value(&'1', false));
+        assert_eq!(None, map_card_value(&'x', true));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            Err(ParseError::new(
+                4,
+                4,
+                "KTJ1T 220",
+                "a card (A, K, Q, J, T or 2-9)"
+            )),
+            part_one("data/malformed.txt")
+        );
+
+        let error = parse_round("KTJ 220", false).unwrap_err();
+        assert_eq!(ParseError::new(1, 1, "KTJ 220", "a hand of 5 cards"), error);
     }
 }
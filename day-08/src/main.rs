@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
-use utils::num::lcm;
-use utils::string::read_string;
+use utils::error::{solve, ParseError, SolveError};
+use utils::num::crt;
+use utils::parse::{delimited, pair, parse_all, position, preceded, tag, take_while1, trim, word};
+use utils::string::{numbered_sections, read_string};
 
 #[derive(Debug)]
 struct Map {
@@ -10,93 +12,284 @@ struct Map {
 }
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 8 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 8 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> u32 {
-    let input = read_string(file_name);
-    let map = parse_nodes(input);
+fn part_one(file_name: &str) -> Result<usize, SolveError> {
+    let map = parse_nodes(&read_string(file_name))?;
 
-    find_output_node(&map, "AAA")
+    if !map.nodes.contains_key("AAA") {
+        return Err(SolveError::no_solution(
+            "the network has no node named 'AAA'",
+        ));
+    }
+    find_cycle(&map, "AAA")
+        .hits
+        .first()
+        .copied()
+        .ok_or_else(|| SolveError::no_solution("the walk from 'AAA' never reaches an output node"))
 }
 
-fn part_two(file_name: &str) -> usize {
-    let input = read_string(file_name);
-    let map = parse_nodes(input);
+fn part_two(file_name: &str) -> Result<usize, SolveError> {
+    let map = parse_nodes(&read_string(file_name))?;
 
-    let starting_nodes: Vec<&String> = map
+    let cycles = map
         .nodes
         .keys()
         .filter(|key| key.ends_with('A'))
+        .map(|node| find_cycle(&map, node))
         .collect::<Vec<_>>();
+    if cycles.is_empty() {
+        return Err(SolveError::no_solution(
+            "there are no ghosts, as no node ends in 'A'",
+        ));
+    }
 
-    let node_distances = starting_nodes
-        .iter()
-        .map(|&node| find_output_node(&map, node) as usize)
-        .collect::<Vec<_>>();
+    all_at_output(&cycles).ok_or_else(|| {
+        SolveError::no_solution("the ghosts never reach their output nodes at the same time")
+    })
+}
 
-    node_distances
-        .iter()
-        .fold(1, |acc, &distance| lcm(acc, distance))
+/**
+ * The path of a single ghost: it walks a fixed lead-in before entering a cycle it never leaves.
+ * Since the walk only depends on the node and the position in the instructions, the cycle is
+ * found once such a pair repeats.
+ */
+#[derive(Debug)]
+struct Cycle {
+    start: usize,
+    length: usize,
+    hits: Vec<usize>,
 }
 
-fn find_output_node(map: &Map, start_node: &str) -> u32 {
+impl Cycle {
+    fn lead_in_hits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.hits.iter().copied().filter(|&hit| hit < self.start)
+    }
+
+    fn cycle_hits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.hits.iter().copied().filter(|&hit| hit >= self.start)
+    }
+
+    fn at_output(&self, step: usize) -> bool {
+        if step < self.start {
+            self.hits.contains(&step)
+        } else {
+            self.hits
+                .contains(&(self.start + (step - self.start) % self.length))
+        }
+    }
+}
+
+fn find_cycle(map: &Map, start_node: &str) -> Cycle {
+    let mut seen: HashMap<(&str, usize), usize> = HashMap::new();
+    let mut hits = vec![];
     let mut current_node: &str = start_node;
     let mut step: usize = 0;
     let input_size = map.input.len();
 
-    while !current_node.ends_with("Z") {
-        let next_step = map.input[step % input_size];
+    loop {
+        if let Some(&start) = seen.get(&(current_node, step % input_size)) {
+            return Cycle {
+                start,
+                length: step - start,
+                hits,
+            };
+        }
+        seen.insert((current_node, step % input_size), step);
 
-        if next_step == 'R' {
-            current_node = &map.nodes.get(current_node).unwrap().1;
-        } else {
-            current_node = &map.nodes.get(current_node).unwrap().0;
+        if current_node.ends_with('Z') {
+            hits.push(step);
         }
 
+        let (left, right) = map.nodes.get(current_node).unwrap();
+        current_node = if map.input[step % input_size] == 'R' {
+            right
+        } else {
+            left
+        };
         step += 1;
     }
+}
+
+/**
+ * The first step at which every ghost is on an output node.
+ *
+ * Either this happens while some ghost is still in its lead-in, which is checked directly, or all
+ * ghosts are cycling, in which case every choice of output node per cycle is a system of
+ * congruences solved with the Chinese Remainder Theorem.
+ */
+fn all_at_output(cycles: &[Cycle]) -> Option<usize> {
+    let lead_in = cycles
+        .iter()
+        .flat_map(|cycle| cycle.lead_in_hits())
+        .filter(|&step| cycles.iter().all(|cycle| cycle.at_output(step)))
+        .min();
+
+    let latest_start = cycles.iter().map(|cycle| cycle.start).max().unwrap_or(0) as i64;
+    let solutions = cycles.iter().fold(vec![(0i64, 1i64)], |solutions, cycle| {
+        solutions
+            .iter()
+            .flat_map(|&solution| {
+                cycle
+                    .cycle_hits()
+                    .filter_map(move |hit| crt([solution, (hit as i64, cycle.length as i64)]))
+            })
+            .collect()
+    });
+    let cycling = solutions
+        .into_iter()
+        .map(|(step, period)| {
+            // the congruences only hold once every ghost has entered its cycle
+            let behind = (latest_start - step).max(0);
+            step + (behind + period - 1) / period * period
+        })
+        .min()
+        .map(|step| step as usize);
 
-    return step as u32;
+    lead_in.into_iter().chain(cycling).min()
 }
 
-fn parse_nodes(input: String) -> Map {
-    let sections = input.trim().split("\n\n").collect::<Vec<_>>();
-    let input = sections[0].trim().chars().collect::<Vec<_>>();
+/**
+ * The instructions, a blank line and the network, where every node a line points to has to be
+ * defined on a line of its own
+ */
+fn parse_nodes(input: &str) -> Result<Map, ParseError> {
+    let mut sections = numbered_sections(input);
 
-    let mut nodes: HashMap<String, (String, String)> = HashMap::new();
+    let (first_line, instructions) = sections
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "", "a line of instructions"))?;
+    let directions = take_while1(|c| c == 'L' || c == 'R', "'L' or 'R'");
+    let input = parse_all(directions, &instructions)
+        .map_err(|e| e.offset_lines(first_line))?
+        .chars()
+        .collect::<Vec<_>>();
 
-    sections[1].lines().for_each(|line| {
-        let parts = line.split('=').collect::<Vec<_>>();
+    let (first_line, network) = sections.next().ok_or_else(|| {
+        ParseError::new(
+            first_line + 2,
+            1,
+            "",
+            "a blank line followed by the network",
+        )
+    })?;
 
-        let start_node = parts[0].trim().to_string();
-        let node_string = parts[1].replace('(', "").replace(')', "");
-        let node_pair = node_string.split(',').collect::<Vec<_>>();
+    let target = || pair(position, word);
+    let node = pair(
+        word,
+        preceded(
+            trim(tag("=")),
+            delimited(
+                tag("("),
+                pair(target(), preceded(trim(tag(",")), target())),
+                tag(")"),
+            ),
+        ),
+    );
+    let lines = network
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            parse_all(&node, line)
+                .map(|node| (index, node))
+                .map_err(|e| e.offset_lines(first_line + index))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
-        let left_node = node_pair[0].trim().to_string();
-        let right_node = node_pair[1].trim().to_string();
+    let mut nodes: HashMap<String, (String, String)> = HashMap::new();
+    for (_, (start_node, ((_, left_node), (_, right_node)))) in &lines {
+        nodes.insert(
+            start_node.to_string(),
+            (left_node.to_string(), right_node.to_string()),
+        );
+    }
 
-        nodes.insert(start_node, (left_node, right_node));
-    });
+    for (index, (_, (left, right))) in &lines {
+        if let Some((at, _)) = [left, right]
+            .into_iter()
+            .find(|(_, target)| !nodes.contains_key(*target))
+        {
+            return Err(at
+                .error("a node defined in the network")
+                .offset_lines(first_line + index));
+        }
+    }
 
-    Map { nodes, input }
+    Ok(Map { nodes, input })
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{part_one, part_two};
+    use utils::error::{ParseError, SolveError};
+
+    use crate::{parse_nodes, part_one, part_two};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(2, part_one("data/example.txt"))
+        assert_eq!(Ok(2), part_one("data/example.txt"))
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(6, part_two("data/example_2.txt"))
+        assert_eq!(Ok(6), part_two("data/example_2.txt"))
+    }
+
+    #[test]
+    fn part_two_offset_cycles_test() {
+        // the ghosts first reach an output node after 2 and 1 steps, but then repeat every 2 and 3
+        assert_eq!(Ok(4), part_two("data/example_3.txt"))
+    }
+
+    #[test]
+    fn part_two_never_aligned_test() {
+        // one ghost is on an output node after every odd step, the other after every even one
+        assert_eq!(
+            Err(SolveError::no_solution(
+                "the ghosts never reach their output nodes at the same time"
+            )),
+            part_two("data/example_4.txt")
+        )
+    }
+
+    #[test]
+    fn no_solution_test() {
+        // AAA and BBB only ever lead to each other on the instructions they are reached with
+        assert_eq!(
+            Err(SolveError::no_solution(
+                "the walk from 'AAA' never reaches an output node"
+            )),
+            part_one("data/example_5.txt")
+        );
+
+        assert_eq!(
+            Err(SolveError::no_solution(
+                "the network has no node named 'AAA'"
+            )),
+            part_one("data/example_6.txt")
+        );
+        assert_eq!(
+            Err(SolveError::no_solution(
+                "there are no ghosts, as no node ends in 'A'"
+            )),
+            part_two("data/example_6.txt")
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = parse_nodes("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA ZZZ)\n").unwrap_err();
+        assert_eq!(ParseError::new(4, 12, "BBB = (AAA ZZZ)", "','"), error);
+
+        // columns count characters, not bytes
+        let error = parse_nodes("L\n\nÄÄÄ = (ÄÄÄ, ÖÖÖ)\n").unwrap_err();
+        assert_eq!(
+            ParseError::new(3, 13, "ÄÄÄ = (ÄÄÄ, ÖÖÖ)", "a node defined in the network"),
+            error
+        );
     }
 }
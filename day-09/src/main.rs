@@ -1,59 +1,45 @@
-use utils::string::read;
+use utils::{
+    error::{solve, ParseError},
+    num::poly::{extrapolate, PolyError},
+    string::{parse_lines, read, try_ints},
+};
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 9 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 9 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> i32 {
+fn part_one(file_name: &str) -> Result<i128, ParseError> {
     let input = read(file_name);
+    let predictions = parse_lines(&input, |line| {
+        let seq = try_ints::<i128>(line)?;
+        predict(line, &seq, seq.len() as i128)
+    })?;
 
-    input
-        .iter()
-        .map(|line| parse_history(line))
-        .map(|seq| predict_value(seq))
-        .sum()
+    Ok(predictions.iter().sum())
 }
 
-fn part_two(file_name: &str) -> i32 {
+fn part_two(file_name: &str) -> Result<i128, ParseError> {
     let input = read(file_name);
+    let predictions = parse_lines(&input, |line| predict(line, &try_ints(line)?, -1))?;
 
-    input
-        .iter()
-        .map(|line| parse_history(line))
-        .map(|seq| seq.iter().copied().rev().collect::<Vec<_>>())
-        .map(|rev_seq| predict_value(rev_seq))
-        .sum()
+    Ok(predictions.iter().sum())
 }
 
-fn predict_value(sequence: Vec<i32>) -> i32 {
-    let mut current_sequence = sequence;
-    let mut last_values: Vec<i32> = vec![];
-
-    while current_sequence.iter().any(|&e| e != 0) {
-        last_values.push(*current_sequence.last().unwrap());
-
-        let next_sequence = current_sequence
-            .windows(2)
-            .map(|win| win[1] - win[0])
-            .collect::<Vec<_>>();
-
-        current_sequence = next_sequence
-    }
-
-    // starting at 0 we can fold the reversed list back up to get our prediction
-    last_values.iter().rev().fold(0, |acc, e| acc + e)
-}
-
-fn parse_history(input: &str) -> Vec<i32> {
-    input
-        .trim()
-        .split_whitespace()
-        .map(|c| c.parse::<i32>().unwrap())
-        .collect::<Vec<_>>()
+/**
+ * The value at position k of the history on the line
+ */
+fn predict(line: &str, seq: &[i128], k: i128) -> Result<i128, ParseError> {
+    extrapolate(seq, k).map_err(|e| {
+        let expected = match e {
+            PolyError::Overflow => "values small enough to extrapolate",
+            _ => "a history generated by a polynomial",
+        };
+        ParseError::new(1, 1, line, expected)
+    })
 }
 
 #[cfg(test)]
@@ -62,13 +48,13 @@ mod test {
 
     #[test]
     fn part_one_test() {
-        assert_eq!(114, part_one("data/example.txt"));
-        assert_eq!(1696140818, part_one("data/input.txt"));
+        assert_eq!(Ok(114), part_one("data/example.txt"));
+        assert_eq!(Ok(1696140818), part_one("data/input.txt"));
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(2, part_two("data/example.txt"));
-        assert_eq!(1152, part_two("data/input.txt"));
+        assert_eq!(Ok(2), part_two("data/example.txt"));
+        assert_eq!(Ok(1152), part_two("data/input.txt"));
     }
 }
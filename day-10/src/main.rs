@@ -1,131 +1,161 @@
-use std::usize;
-
-use utils::string::read_string;
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Direction {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
-}
+use utils::{
+    error::{solve, ParseError},
+    geometry::Polygon,
+    grid::Grid,
+    point::{Direction, Directional, Point},
+    string::read_string,
+};
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 10 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 10 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> i32 {
+fn part_one(file_name: &str) -> Result<i64, ParseError> {
     let input = read_string(file_name);
-    let (cost, _) = parse_grid(&input);
-    cost
+    let (cost, _) = parse_grid(&input)?;
+    Ok(cost)
 }
 
-fn part_two(file_name: &str) -> i32 {
+fn part_two(file_name: &str) -> Result<i64, ParseError> {
     let input = read_string(file_name);
-    let (_, area) = parse_grid(&input);
-    area
+    let (_, area) = parse_grid(&input)?;
+    Ok(area)
 }
 
 /**
- * To calculate the area we use the Shoelace formula (https://en.wikipedia.org/wiki/Shoelace_formula)
- * along with Pick's Theorem (https://en.wikipedia.org/wiki/Pick%27s_theorem) (here to find the interior points).
- * The rearranged formula looks like:
- *      A = i + b/2 - 1 -> i = A - b / 2 - 1
- *
- * To use this we need to track the corners. The first corner is the start position.
+ * Walk the loop and collect its corners, starting at the start position. The loop length is the
+ * number of boundary points of the resulting polygon, and the enclosed tiles are its interior points.
  */
-fn parse_grid(input: &str) -> (i32, i32) {
-    let grid = input
-        .trim()
-        .lines()
-        .map(|line| line.trim().chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let width = grid[0].len();
-
-    let mut corner = grid
-        .iter()
-        .flatten()
-        .position(|&h| h == 'S')
-        .map(|idx| (idx % width, idx / width))
-        .unwrap();
-
-    // TODO: This could be extracted into utils
-    let follow = |(x, y): (usize, usize), direction: Direction| match direction {
-        Direction::UP => (x, y - 1),
-        Direction::DOWN => (x, y + 1),
-        Direction::LEFT => (x - 1, y),
-        Direction::RIGHT => (x + 1, y),
-    };
-
-    // To make things easier we only follow either up or down on the initial corner (not left or right)
-    let mut direction = if matches!(grid[corner.1 - 1][corner.0], '|' | '7' | 'F') {
-        Direction::UP
-    } else {
-        Direction::DOWN
+fn parse_grid(input: &str) -> Result<(i64, i64), ParseError> {
+    // skip blank lines before the grid, counting them so errors keep the line numbers of the file
+    let (skipped, start) = input
+        .split_inclusive('\n')
+        .take_while(|line| line.trim().is_empty())
+        .fold((0, 0), |(lines, bytes), line| {
+            (lines + 1, bytes + line.len())
+        });
+    let input = input[start..].trim_end();
+    let grid = Grid::parse_tiles(input, "|-LJ7F.S").map_err(|e| e.offset_lines(skipped))?;
+
+    let start = grid.find('S').map(Point::from).ok_or_else(|| {
+        let line = input.lines().next().unwrap_or("");
+        ParseError::new(skipped + 1, 1, line, "a start tile 'S'")
+    })?;
+    // a pipe that leads off the grid or into a tile the loop cannot continue through
+    let broken = |point: Point| {
+        let (x, y) = (point.x.max(0) as usize, point.y.max(0) as usize);
+        let line = input.lines().nth(y).unwrap_or("");
+        ParseError::new(skipped + y + 1, x + 1, line, "a pipe continuing the loop")
     };
-    let mut current = follow(corner, direction);
+    let tile = |point: Point| grid.get(point).copied().ok_or_else(|| broken(point));
 
-    let mut steps = 1;
-    let mut area = 0;
+    // set off towards the first neighbour that leads back into the start
+    let mut direction = Direction::all()
+        .into_iter()
+        .find(|&direction| {
+            grid.get(start.follow(&direction).0)
+                .and_then(|&tile| openings(tile))
+                .is_some_and(|openings| openings.contains(&direction.opposite()))
+        })
+        .ok_or_else(|| broken(start))?;
 
-    let determinant =
-        |a: (usize, usize), b: (usize, usize)| a.0 as i32 * b.1 as i32 - a.1 as i32 * b.0 as i32;
+    let mut corners = vec![start];
+    let mut current = start.follow(&direction).0;
 
     loop {
-        // simply follow straight paths - can skip doing fancy maths here...
-        while grid[current.1][current.0] == '-' || grid[current.1][current.0] == '|' {
-            current = follow(current, direction); // reuse the direction since we're moving in a straight line
-            steps += 1
+        let tile = tile(current)?;
+        if tile == 'S' {
+            break; // back at the start
         }
 
-        direction = match grid[current.1][current.0] {
-            '7' if direction == Direction::UP => Direction::LEFT,
-            'F' if direction == Direction::UP => Direction::RIGHT,
-            'J' if direction == Direction::DOWN => Direction::LEFT,
-            'L' if direction == Direction::DOWN => Direction::RIGHT,
-            'J' | 'L' => Direction::UP,
-            '7' | 'F' => Direction::DOWN,
-            _ => {
-                area += determinant(corner, current);
-                break;
-            }
-        };
-
-        // update steps and the shoelace area
-        steps += 1;
-        area += determinant(corner, current);
-
-        // we just turned a corner!
-        corner = current;
-        current = follow(current, direction);
+        // the pipe has to connect back to where we came from, and its other end is the way on
+        let back = direction.opposite();
+        let next = openings(tile)
+            .filter(|openings| openings.contains(&back))
+            .map(|[one, other]| if one == back { other } else { one })
+            .ok_or_else(|| broken(current))?;
+
+        // only the corners matter for the polygon
+        if next != direction {
+            corners.push(current);
+        }
+        direction = next;
+        current = current.follow(&direction).0;
     }
 
-    let furthest_distance = steps / 2;
-    let area = area.abs() / 2;
-    let interior_points = area - steps / 2 + 1;
-    (furthest_distance, interior_points)
+    let pipe_loop = Polygon::new(corners);
+    let furthest_distance = pipe_loop.boundary_points() / 2;
+    let interior_points = pipe_loop.interior_points();
+    Ok((furthest_distance as i64, interior_points as i64))
+}
+
+/**
+ * The two directions a pipe leads to, or None for ground and the start
+ */
+fn openings(tile: char) -> Option<[Direction; 2]> {
+    match tile {
+        '|' => Some([Direction::UP, Direction::DOWN]),
+        '-' => Some([Direction::LEFT, Direction::RIGHT]),
+        'L' => Some([Direction::UP, Direction::RIGHT]),
+        'J' => Some([Direction::UP, Direction::LEFT]),
+        '7' => Some([Direction::DOWN, Direction::LEFT]),
+        'F' => Some([Direction::DOWN, Direction::RIGHT]),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{part_one, part_two};
+    use utils::error::ParseError;
+
+    use crate::{parse_grid, part_one, part_two};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(4, part_one("data/example.txt"));
-        assert_eq!(8, part_one("data/example_2.txt"));
-        assert_eq!(6875, part_one("data/input.txt"));
+        assert_eq!(Ok(4), part_one("data/example.txt"));
+        assert_eq!(Ok(8), part_one("data/example_2.txt"));
+        assert_eq!(Ok(6875), part_one("data/input.txt"));
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(1, part_two("data/example.txt"));
-        assert_eq!(4, part_two("data/example_3.txt"));
-        assert_eq!(471, part_two("data/input.txt"));
+        assert_eq!(Ok(1), part_two("data/example.txt"));
+        assert_eq!(Ok(4), part_two("data/example_3.txt"));
+        assert_eq!(Ok(471), part_two("data/input.txt"));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            Err(ParseError::new(3, 1, "...", "a start tile 'S'")),
+            parse_grid("\n \n...\n...\n")
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                3,
+                4,
+                ".S-x.",
+                "one of the tiles '|-LJ7F.S'"
+            )),
+            parse_grid("\n.....\n.S-x.\n")
+        );
+        // no pipe leads into the start, the loop below it is entered from a closed side
+        assert_eq!(
+            Err(ParseError::new(1, 1, "S.", "a pipe continuing the loop")),
+            parse_grid("S.\nF7\nLJ\n")
+        );
+    }
+
+    #[test]
+    fn malformed_test() {
+        // the pipe below the 7 is entered from above but only leads left and right
+        assert_eq!(
+            Err(ParseError::new(3, 4, ".|.-.", "a pipe continuing the loop")),
+            part_one("data/malformed.txt")
+        );
     }
 }
@@ -1,27 +1,42 @@
-use utils::{grid::Grid, string::read_string, vector::UniquePermutations};
+use utils::{
+    error::{solve, ParseError},
+    grid::Grid,
+    string::read_string,
+    vector::UniquePermutations,
+};
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 11 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt", 1_000_000);
+    let part_two_solution = solve("data/input.txt", |file_name| part_two(file_name, 1_000_000));
     println!("Day 11 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> usize {
+fn part_one(file_name: &str) -> Result<usize, ParseError> {
     let input = read_string(file_name);
-    let universe = Grid::parse(&input);
+    let universe = Grid::parse_tiles(&input, ".#")?;
     let (row_expansion, col_expansion) = universe_expansion(&universe);
 
-    find_inter_galactic_distances(universe, row_expansion, col_expansion, 2)
+    Ok(find_inter_galactic_distances(
+        universe,
+        row_expansion,
+        col_expansion,
+        2,
+    ))
 }
 
-fn part_two(file_name: &str, expansion_factor: usize) -> usize {
+fn part_two(file_name: &str, expansion_factor: usize) -> Result<usize, ParseError> {
     let input = read_string(file_name);
-    let universe = Grid::parse(&input);
+    let universe = Grid::parse_tiles(&input, ".#")?;
     let (row_expansion, col_expansion) = universe_expansion(&universe);
 
-    find_inter_galactic_distances(universe, row_expansion, col_expansion, expansion_factor)
+    Ok(find_inter_galactic_distances(
+        universe,
+        row_expansion,
+        col_expansion,
+        expansion_factor,
+    ))
 }
 
 fn find_inter_galactic_distances(
@@ -58,14 +73,14 @@ fn find_inter_galactic_distances(
 
 fn universe_expansion(universe: &Grid<char>) -> (Vec<usize>, Vec<usize>) {
     let mut row_expansion = vec![];
-    for row_idx in 0..universe.height {
-        if universe.row(row_idx).iter().all(|&&c| c == '.') {
+    for row_idx in 0..universe.height() {
+        if universe.row(row_idx).unwrap().iter().all(|&&c| c == '.') {
             row_expansion.push(row_idx)
         }
     }
     let mut col_expansion = vec![];
-    for col_idx in 0..universe.width {
-        if universe.col(col_idx).iter().all(|&&c| c == '.') {
+    for col_idx in 0..universe.width() {
+        if universe.col(col_idx).unwrap().iter().all(|&&c| c == '.') {
             col_expansion.push(col_idx)
         }
     }
@@ -78,12 +93,12 @@ mod test {
 
     #[test]
     fn part_one_test() {
-        assert_eq!(374, part_one("data/example.txt"));
+        assert_eq!(Ok(374), part_one("data/example.txt"));
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(1030, part_two("data/example.txt", 10));
-        assert_eq!(8410, part_two("data/example.txt", 100));
+        assert_eq!(Ok(1030), part_two("data/example.txt", 10));
+        assert_eq!(Ok(8410), part_two("data/example.txt", 100));
     }
 }
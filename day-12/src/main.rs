@@ -1,35 +1,39 @@
 use std::collections::HashMap;
 
-use utils::string::read_string;
+use utils::{
+    error::{solve, ParseError},
+    parse::{integer, pair, parse_all, separated, tag, take_while1, trim},
+    string::read_string,
+};
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 12 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 12 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> usize {
+fn part_one(file_name: &str) -> Result<usize, ParseError> {
     let input = read_string(file_name);
 
-    parse_hotsprings(&input)
+    Ok(parse_hotsprings(&input)?
         .iter()
-        .map(|(pattern, counts)| spring_arrangements(&pattern, counts))
-        .sum()
+        .map(|(pattern, counts)| spring_arrangements(pattern, counts))
+        .sum())
 }
 
-fn part_two(file_name: &str) -> usize {
+fn part_two(file_name: &str) -> Result<usize, ParseError> {
     let input = read_string(file_name);
 
-    parse_hotsprings(&input)
+    Ok(parse_hotsprings(&input)?
         .iter()
         .map(|(pattern, counts)| {
             let pattern = [*pattern; 5].join("?");
             let counts = counts.repeat(5);
             spring_arrangements(&pattern, &counts)
         })
-        .sum()
+        .sum())
 }
 
 fn combinations(
@@ -84,37 +88,47 @@ fn spring_arrangements(springs: &str, counts: &[usize]) -> usize {
     combinations(&mut cache, &chars, None, counts)
 }
 
-fn parse_hotsprings(input: &str) -> Vec<(&str, Vec<usize>)> {
+fn parse_hotsprings(input: &str) -> Result<Vec<(&str, Vec<usize>)>, ParseError> {
+    let row = pair(
+        take_while1(|c| ".#?".contains(c), "springs ('.', '#' or '?')"),
+        trim(separated(integer::<usize>(), tag(","))),
+    );
+
     input
         .lines()
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-            let pattern = parts.next().unwrap();
-            let counts = parts
-                .next()
-                .unwrap()
-                .split(',')
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
-            (pattern, counts)
-        })
-        .collect::<Vec<_>>()
+        .enumerate()
+        .map(|(index, line)| parse_all(&row, line).map_err(|e| e.offset_lines(index)))
+        .collect()
 }
 
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
 
-    use crate::{combinations, part_one, part_two};
+    use utils::error::ParseError;
+
+    use crate::{combinations, parse_hotsprings, part_one, part_two};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(21, part_one("data/example.txt"));
+        assert_eq!(Ok(21), part_one("data/example.txt"));
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(525152, part_two("data/example.txt"));
+        assert_eq!(Ok(525152), part_two("data/example.txt"));
+    }
+
+    #[test]
+    fn parse_hotsprings_test() {
+        assert_eq!(
+            Ok(vec![("???.###", vec![1, 1, 3]), ("#?", vec![2])]),
+            parse_hotsprings("???.###  1,1,3\n#? 2\n")
+        );
+        assert_eq!(
+            Err(ParseError::new(6, 16, "?###???????? 3,,1", "an integer")),
+            part_one("data/malformed.txt")
+        );
     }
 
     #[test]
@@ -1,62 +1,75 @@
-use std::{collections::HashSet, error, usize};
-
-use utils::{grid::Grid, string::read_string};
+use utils::{
+    error::{solve, ParseError},
+    grid::Grid,
+    string::{numbered_sections, read_string},
+};
 
 type SolutionType = usize;
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 13 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 13 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> SolutionType {
-    let input = read_string(file_name);
-    let mirrors = parse_mirrors(&input);
-    mirrors
-        .iter()
-        .map(|mirror| find_mirror_line(mirror, 0).unwrap())
-        .sum()
+fn part_one(file_name: &str) -> Result<SolutionType, ParseError> {
+    summarize_mirrors(file_name, |mirror| find_mirror_line(mirror, 0))
 }
 
-fn part_two(file_name: &str) -> SolutionType {
-    let input = read_string(file_name);
-    let mirrors = parse_mirrors(&input);
-    mirrors
-        .iter()
-        .map(|mirror: &Grid<char>| find_alternate_mirror_line(mirror))
-        .sum()
+fn part_two(file_name: &str) -> Result<SolutionType, ParseError> {
+    summarize_mirrors(file_name, find_alternate_mirror_line)
 }
 
-fn parse_mirrors(input: &str) -> Vec<Grid<char>> {
-    input.split("\n\n").map(|i| Grid::parse(i)).collect()
+/**
+ * The sum of the mirror lines of every pattern, where a pattern without one is an input error
+ */
+fn summarize_mirrors(
+    file_name: &str,
+    mirror_line: impl Fn(&Grid<char>) -> Option<usize>,
+) -> Result<SolutionType, ParseError> {
+    let input = read_string(file_name);
+
+    let mut total = 0;
+    for (first_line, section) in numbered_sections(&input) {
+        let mirror = Grid::parse_tiles(&section, ".#").map_err(|e| e.offset_lines(first_line))?;
+        total += mirror_line(&mirror).ok_or_else(|| {
+            let row = section.lines().next().unwrap_or("");
+            ParseError::new(
+                first_line + 1,
+                1,
+                row,
+                "a pattern with a line of reflection",
+            )
+        })?;
+    }
+    Ok(total)
 }
 
-fn find_alternate_mirror_line(mirror: &Grid<char>) -> usize {
-    find_mirror_line(mirror, 1).unwrap()
+fn find_alternate_mirror_line(mirror: &Grid<char>) -> Option<usize> {
+    find_mirror_line(mirror, 1)
 }
 
 fn find_mirror_line(mirror: &Grid<char>, allowed_errrors: usize) -> Option<usize> {
-    if let Some(horizontal_match) = find_horizontal_line(&mirror, allowed_errrors) {
+    if let Some(horizontal_match) = find_horizontal_line(mirror, allowed_errrors) {
         return Some(horizontal_match * 100);
     }
-    if let Some(vertical_match) = find_vertical_line(&mirror, allowed_errrors) {
+    if let Some(vertical_match) = find_vertical_line(mirror, allowed_errrors) {
         return Some(vertical_match);
     }
     None
 }
 
 fn find_vertical_line(mirror: &Grid<char>, allowed_errrors: usize) -> Option<usize> {
-    for v_line in 1..mirror.width {
+    for v_line in 1..mirror.width() {
         let left_side = (0..v_line)
             .rev()
-            .map(|index| mirror.col(index))
+            .map(|index| mirror.col(index).unwrap())
             .collect::<Vec<_>>();
 
-        let right_side = (v_line..mirror.width)
-            .map(|index| mirror.col(index))
+        let right_side = (v_line..mirror.width())
+            .map(|index| mirror.col(index).unwrap())
             .collect::<Vec<_>>();
 
         let cutoff = left_side.len().min(right_side.len());
@@ -74,8 +87,6 @@ fn find_vertical_line(mirror: &Grid<char>, allowed_errrors: usize) -> Option<usi
             }
         }
 
-        println!("Number of errors: {}", errors);
-
         if errors == allowed_errrors {
             return Some(v_line);
         }
@@ -84,14 +95,14 @@ fn find_vertical_line(mirror: &Grid<char>, allowed_errrors: usize) -> Option<usi
 }
 
 fn find_horizontal_line(mirror: &Grid<char>, allowed_errrors: usize) -> Option<usize> {
-    for h_line in 1..mirror.height {
+    for h_line in 1..mirror.height() {
         let top_side = (0..h_line)
             .rev()
-            .map(|index| mirror.row(index))
+            .map(|index| mirror.row(index).unwrap())
             .collect::<Vec<_>>();
 
-        let bottom_side = (h_line..mirror.height)
-            .map(|index| mirror.row(index))
+        let bottom_side = (h_line..mirror.height())
+            .map(|index| mirror.row(index).unwrap())
             .collect::<Vec<_>>();
 
         let cutoff = top_side.len().min(bottom_side.len());
@@ -118,79 +129,64 @@ fn find_horizontal_line(mirror: &Grid<char>, allowed_errrors: usize) -> Option<u
 
 #[cfg(test)]
 mod test {
-    use utils::grid::Grid;
+    use utils::grid;
 
     use crate::{find_alternate_mirror_line, find_mirror_line, part_one, part_two};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(405, part_one("data/example.txt"));
-        assert_eq!(34911, part_one("data/input.txt"));
+        assert_eq!(Ok(405), part_one("data/example.txt"));
+        assert_eq!(Ok(34911), part_one("data/input.txt"));
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(400, part_two("data/example.txt"));
-        assert_eq!(33183, part_two("data/input.txt"));
+        assert_eq!(Ok(400), part_two("data/example.txt"));
+        assert_eq!(Ok(33183), part_two("data/input.txt"));
     }
 
     #[test]
     fn mirror_line_test() {
-        let mirror = Grid {
-            width: 9,
-            height: 7,
-            data: [
-                '#', '.', '#', '#', '.', '.', '#', '#', '.', /* formatting */
-                '.', '.', '#', '.', '#', '#', '.', '#', '.', /* formatting */
-                '#', '#', '.', '.', '.', '.', '.', '.', '#', /* formatting */
-                '#', '#', '.', '.', '.', '.', '.', '.', '#', /* formatting */
-                '.', '.', '#', '.', '#', '#', '.', '#', '.', /* formatting */
-                '.', '.', '#', '#', '.', '.', '#', '#', '.', /* formatting */
-                '#', '.', '#', '.', '#', '#', '.', '#', '.', /* formatting */
-            ]
-            .to_vec(),
-        };
+        let mirror = grid![
+            "#.##..##.",
+            "..#.##.#.",
+            "##......#",
+            "##......#",
+            "..#.##.#.",
+            "..##..##.",
+            "#.#.##.#.",
+        ];
 
         assert_eq!(Some(5), find_mirror_line(&mirror, 0));
     }
 
     #[test]
     fn alternate_mirror_line_test() {
-        let mirror = Grid {
-            width: 9,
-            height: 7,
-            data: [
-                '#', '.', '#', '#', '.', '.', '#', '#', '.', /* formatting */
-                '.', '.', '#', '.', '#', '#', '.', '#', '.', /* formatting */
-                '#', '#', '.', '.', '.', '.', '.', '.', '#', /* formatting */
-                '#', '#', '.', '.', '.', '.', '.', '.', '#', /* formatting */
-                '.', '.', '#', '.', '#', '#', '.', '#', '.', /* formatting */
-                '.', '.', '#', '#', '.', '.', '#', '#', '.', /* formatting */
-                '#', '.', '#', '.', '#', '#', '.', '#', '.', /* formatting */
-            ]
-            .to_vec(),
-        };
-
-        assert_eq!(300, find_alternate_mirror_line(&mirror));
+        let mirror = grid![
+            "#.##..##.",
+            "..#.##.#.",
+            "##......#",
+            "##......#",
+            "..#.##.#.",
+            "..##..##.",
+            "#.#.##.#.",
+        ];
+
+        assert_eq!(Some(300), find_alternate_mirror_line(&mirror));
     }
 
     #[test]
     fn second_alternate_mirror_line_test() {
-        let mirror = Grid {
-            width: 9,
-            height: 7,
-            data: [
-                '#', '.', '.', '.', '#', '#', '.', '.', '#', /* formatting */
-                '#', '.', '.', '.', '.', '#', '.', '.', '#', /* formatting */
-                '.', '.', '#', '#', '.', '.', '#', '#', '#', /* formatting */
-                '#', '#', '#', '#', '#', '.', '#', '#', '.', /* formatting */
-                '#', '#', '#', '#', '#', '.', '#', '#', '.', /* formatting */
-                '.', '.', '#', '#', '.', '.', '#', '#', '#', /* formatting */
-                '#', '.', '.', '.', '.', '#', '.', '.', '#',
-            ]
-            .to_vec(),
-        };
-
-        assert_eq!(100, find_alternate_mirror_line(&mirror));
+        let mirror = grid![
+            "#...##..#",
+            "#....#..#",
+            "..##..###",
+            "#####.##.",
+            "#####.##.",
+            "..##..###",
+            "#....#..#",
+        ];
+
+        assert_eq!(Some(100), find_alternate_mirror_line(&mirror));
     }
 }
@@ -1,39 +1,36 @@
 use std::collections::{HashMap, VecDeque};
 
-use utils::{grid::Grid, string::read_string};
+use utils::{
+    error::{solve, ParseError},
+    grid::Grid,
+    point::Direction,
+    string::read_string,
+};
 
 type SolutionType = usize;
 
-#[derive(Debug)]
-enum Direction {
-    NORTH,
-    WEST,
-    SOUTH,
-    EAST,
-}
-
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 14 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 14 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> SolutionType {
+fn part_one(file_name: &str) -> Result<SolutionType, ParseError> {
     let input = read_string(file_name);
-    let grid = Grid::parse(&input);
+    let grid = Grid::parse_tiles(&input, ".#O")?;
 
-    let new_grid = tilt(grid, Direction::NORTH);
-    calculate_load(&new_grid)
+    let new_grid = tilt(grid, Direction::UP);
+    Ok(calculate_load(&new_grid))
 }
 
-fn part_two(file_name: &str) -> SolutionType {
+fn part_two(file_name: &str) -> Result<SolutionType, ParseError> {
     let input = read_string(file_name);
-    let grid = Grid::parse(&input);
+    let grid = Grid::parse_tiles(&input, ".#O")?;
     let new_grid = run_cycles(grid, 1_000_000_000);
 
-    calculate_load(&new_grid)
+    Ok(calculate_load(&new_grid))
 }
 
 fn run_cycles(mut grid: Grid<char>, cycles: usize) -> Grid<char> {
@@ -47,10 +44,10 @@ fn run_cycles(mut grid: Grid<char>, cycles: usize) -> Grid<char> {
         }
 
         let original = grid.clone();
-        grid = tilt(grid, Direction::NORTH);
-        grid = tilt(grid, Direction::WEST);
-        grid = tilt(grid, Direction::SOUTH);
-        grid = tilt(grid, Direction::EAST);
+        grid = tilt(grid, Direction::UP);
+        grid = tilt(grid, Direction::LEFT);
+        grid = tilt(grid, Direction::DOWN);
+        grid = tilt(grid, Direction::RIGHT);
 
         memo.insert(original, (grid.clone(), index));
         memo_index.insert(index, grid.clone());
@@ -67,7 +64,7 @@ fn run_cycles(mut grid: Grid<char>, cycles: usize) -> Grid<char> {
     memo_index.get(&final_index).unwrap().clone()
 }
 
-fn tilt_section(section: &Vec<&char>) -> Vec<char> {
+fn tilt_section(section: &[&char]) -> Vec<char> {
     let (mut new_column, mut section) = section.iter().fold(
         (vec![], VecDeque::new()),
         |(mut new_column, mut section), &&c| {
@@ -95,38 +92,38 @@ fn tilt_section(section: &Vec<&char>) -> Vec<char> {
 
 fn tilt(grid: Grid<char>, direction: Direction) -> Grid<char> {
     match direction {
-        Direction::NORTH => Grid::from_cols(
+        Direction::UP => Grid::from_cols(
             grid.columns()
                 .iter()
                 .map(|column| tilt_section(column))
                 .collect::<Vec<_>>(),
         ),
-        Direction::WEST => Grid::from_rows(
+        Direction::LEFT => Grid::from_rows(
             grid.rows()
                 .iter()
                 .map(|row| tilt_section(row))
                 .collect::<Vec<_>>(),
         ),
-        Direction::SOUTH => Grid::from_cols(
+        Direction::DOWN => Grid::from_cols(
             grid.columns()
                 .iter()
                 .map(|column| {
-                    tilt_section(&column.iter().rev().map(|&c| c).collect::<Vec<_>>())
+                    tilt_section(&column.iter().rev().copied().collect::<Vec<_>>())
                         .iter()
                         .rev()
-                        .map(|&c| c)
+                        .copied()
                         .collect::<Vec<_>>()
                 })
                 .collect::<Vec<_>>(),
         ),
-        Direction::EAST => Grid::from_rows(
+        Direction::RIGHT => Grid::from_rows(
             grid.rows()
                 .iter()
                 .map(|row| {
-                    tilt_section(&row.iter().rev().map(|&c| c).collect::<Vec<_>>())
+                    tilt_section(&row.iter().rev().copied().collect::<Vec<_>>())
                         .iter()
                         .rev()
-                        .map(|&c| c)
+                        .copied()
                         .collect::<Vec<_>>()
                 })
                 .collect::<Vec<_>>(),
@@ -141,7 +138,7 @@ fn calculate_load(grid: &Grid<char>) -> usize {
             column
                 .iter()
                 .enumerate()
-                .map(|(index, &&c)| if c == 'O' { grid.height - index } else { 0 })
+                .map(|(index, &&c)| if c == 'O' { grid.height() - index } else { 0 })
                 .sum::<usize>()
         })
         .sum()
@@ -149,181 +146,141 @@ fn calculate_load(grid: &Grid<char>) -> usize {
 
 #[cfg(test)]
 mod test {
-    use utils::grid::Grid;
+    use utils::grid;
 
     use crate::{part_one, part_two, tilt, Direction};
 
     #[test]
     fn part_one_test() {
-        assert_eq!(136, part_one("data/example.txt"));
+        assert_eq!(Ok(136), part_one("data/example.txt"));
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(64, part_two("data/example.txt"));
+        assert_eq!(Ok(64), part_two("data/example.txt"));
     }
 
     #[test]
     fn cycle_north_test() {
-        let grid = Grid {
-            width: 10,
-            height: 10,
-            data: [
-                'O', '.', '.', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-                'O', '.', 'O', 'O', '#', '.', '.', '.', '.', '#', /* fmt */
-                '.', '.', '.', '.', '.', '#', '#', '.', '.', '.', /* fmt */
-                'O', 'O', '.', '#', 'O', '.', '.', '.', '.', 'O', /* fmt */
-                '.', 'O', '.', '.', '.', '.', '.', 'O', '#', '.', /* fmt */
-                'O', '.', '#', '.', '.', 'O', '.', '#', '.', '#', /* fmt */
-                '.', '.', 'O', '.', '.', '#', 'O', '.', '.', 'O', /* fmt */
-                '.', '.', '.', '.', '.', '.', '.', 'O', '.', '.', /* fmt */
-                '#', '.', '.', '.', '.', '#', '#', '#', '.', '.', /* fmt */
-                '#', 'O', 'O', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-            ]
-            .to_vec(),
-        };
-
-        let expected_grid = Grid {
-            width: 10,
-            height: 10,
-            data: [
-                'O', 'O', 'O', 'O', '.', '#', '.', 'O', '.', '.', /* fmt */
-                'O', 'O', '.', '.', '#', '.', '.', '.', '.', '#', /* fmt */
-                'O', 'O', '.', '.', 'O', '#', '#', '.', '.', 'O', /* fmt */
-                'O', '.', '.', '#', '.', 'O', 'O', '.', '.', '.', /* fmt */
-                '.', '.', '.', '.', '.', '.', '.', '.', '#', '.', /* fmt */
-                '.', '.', '#', '.', '.', '.', '.', '#', '.', '#', /* fmt */
-                '.', '.', 'O', '.', '.', '#', '.', 'O', '.', 'O', /* fmt */
-                '.', '.', 'O', '.', '.', '.', '.', '.', '.', '.', /* fmt */
-                '#', '.', '.', '.', '.', '#', '#', '#', '.', '.', /* fmt */
-                '#', '.', '.', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-            ]
-            .to_vec(),
-        };
-
-        assert_eq!(tilt(grid, Direction::NORTH).data, expected_grid.data);
+        let grid = grid![
+            "O....#....",
+            "O.OO#....#",
+            ".....##...",
+            "OO.#O....O",
+            ".O.....O#.",
+            "O.#..O.#.#",
+            "..O..#O..O",
+            ".......O..",
+            "#....###..",
+            "#OO..#....",
+        ];
+
+        let expected_grid = grid![
+            "OOOO.#.O..",
+            "OO..#....#",
+            "OO..O##..O",
+            "O..#.OO...",
+            "........#.",
+            "..#....#.#",
+            "..O..#.O.O",
+            "..O.......",
+            "#....###..",
+            "#....#....",
+        ];
+
+        assert_eq!(tilt(grid, Direction::UP), expected_grid);
     }
 
     #[test]
     fn cycle_south_test() {
-        let grid = Grid {
-            width: 10,
-            height: 10,
-            data: [
-                'O', '.', '.', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-                'O', '.', 'O', 'O', '#', '.', '.', '.', '.', '#', /* fmt */
-                '.', '.', '.', '.', '.', '#', '#', '.', '.', '.', /* fmt */
-                'O', 'O', '.', '#', 'O', '.', '.', '.', '.', 'O', /* fmt */
-                '.', 'O', '.', '.', '.', '.', '.', 'O', '#', '.', /* fmt */
-                'O', '.', '#', '.', '.', 'O', '.', '#', '.', '#', /* fmt */
-                '.', '.', 'O', '.', '.', '#', 'O', '.', '.', 'O', /* fmt */
-                '.', '.', '.', '.', '.', '.', '.', 'O', '.', '.', /* fmt */
-                '#', '.', '.', '.', '.', '#', '#', '#', '.', '.', /* fmt */
-                '#', 'O', 'O', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-            ]
-            .to_vec(),
-        };
-
-        let expected_grid = Grid {
-            width: 10,
-            height: 10,
-            data: [
-                '.', '.', '.', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-                '.', '.', '.', '.', '#', '.', '.', '.', '.', '#', /* fmt */
-                '.', '.', '.', 'O', '.', '#', '#', '.', '.', '.', /* fmt */
-                '.', '.', '.', '#', '.', '.', '.', '.', '.', '.', /* fmt */
-                'O', '.', 'O', '.', '.', '.', '.', 'O', '#', 'O', /* fmt */
-                'O', '.', '#', '.', '.', 'O', '.', '#', '.', '#', /* fmt */
-                'O', '.', '.', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-                'O', 'O', '.', '.', '.', '.', 'O', 'O', '.', '.', /* fmt */
-                '#', 'O', 'O', '.', '.', '#', '#', '#', '.', '.', /* fmt */
-                '#', 'O', 'O', '.', 'O', '#', '.', '.', '.', 'O', /* fmt */
-            ]
-            .to_vec(),
-        };
-
-        assert_eq!(tilt(grid, Direction::SOUTH).data, expected_grid.data);
+        let grid = grid![
+            "O....#....",
+            "O.OO#....#",
+            ".....##...",
+            "OO.#O....O",
+            ".O.....O#.",
+            "O.#..O.#.#",
+            "..O..#O..O",
+            ".......O..",
+            "#....###..",
+            "#OO..#....",
+        ];
+
+        let expected_grid = grid![
+            ".....#....",
+            "....#....#",
+            "...O.##...",
+            "...#......",
+            "O.O....O#O",
+            "O.#..O.#.#",
+            "O....#....",
+            "OO....OO..",
+            "#OO..###..",
+            "#OO.O#...O",
+        ];
+
+        assert_eq!(tilt(grid, Direction::DOWN), expected_grid);
     }
 
     #[test]
     fn cycle_west_test() {
-        let grid = Grid {
-            width: 10,
-            height: 10,
-            data: [
-                'O', '.', '.', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-                'O', '.', 'O', 'O', '#', '.', '.', '.', '.', '#', /* fmt */
-                '.', '.', '.', '.', '.', '#', '#', '.', '.', '.', /* fmt */
-                'O', 'O', '.', '#', 'O', '.', '.', '.', '.', 'O', /* fmt */
-                '.', 'O', '.', '.', '.', '.', '.', 'O', '#', '.', /* fmt */
-                'O', '.', '#', '.', '.', 'O', '.', '#', '.', '#', /* fmt */
-                '.', '.', 'O', '.', '.', '#', 'O', '.', '.', 'O', /* fmt */
-                '.', '.', '.', '.', '.', '.', '.', 'O', '.', '.', /* fmt */
-                '#', '.', '.', '.', '.', '#', '#', '#', '.', '.', /* fmt */
-                '#', 'O', 'O', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-            ]
-            .to_vec(),
-        };
-
-        let expected_grid = Grid {
-            width: 10,
-            height: 10,
-            data: [
-                'O', '.', '.', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-                'O', 'O', 'O', '.', '#', '.', '.', '.', '.', '#', /* fmt */
-                '.', '.', '.', '.', '.', '#', '#', '.', '.', '.', /* fmt */
-                'O', 'O', '.', '#', 'O', 'O', '.', '.', '.', '.', /* fmt */
-                'O', 'O', '.', '.', '.', '.', '.', '.', '#', '.', /* fmt */
-                'O', '.', '#', 'O', '.', '.', '.', '#', '.', '#', /* fmt */
-                'O', '.', '.', '.', '.', '#', 'O', 'O', '.', '.', /* fmt */
-                'O', '.', '.', '.', '.', '.', '.', '.', '.', '.', /* fmt */
-                '#', '.', '.', '.', '.', '#', '#', '#', '.', '.', /* fmt */
-                '#', 'O', 'O', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-            ]
-            .to_vec(),
-        };
-
-        assert_eq!(tilt(grid, Direction::WEST).data, expected_grid.data);
+        let grid = grid![
+            "O....#....",
+            "O.OO#....#",
+            ".....##...",
+            "OO.#O....O",
+            ".O.....O#.",
+            "O.#..O.#.#",
+            "..O..#O..O",
+            ".......O..",
+            "#....###..",
+            "#OO..#....",
+        ];
+
+        let expected_grid = grid![
+            "O....#....",
+            "OOO.#....#",
+            ".....##...",
+            "OO.#OO....",
+            "OO......#.",
+            "O.#O...#.#",
+            "O....#OO..",
+            "O.........",
+            "#....###..",
+            "#OO..#....",
+        ];
+
+        assert_eq!(tilt(grid, Direction::LEFT), expected_grid);
     }
 
     #[test]
     fn cycle_east_test() {
-        let grid = Grid {
-            width: 10,
-            height: 10,
-            data: [
-                'O', '.', '.', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-                'O', '.', 'O', 'O', '#', '.', '.', '.', '.', '#', /* fmt */
-                '.', '.', '.', '.', '.', '#', '#', '.', '.', '.', /* fmt */
-                'O', 'O', '.', '#', 'O', '.', '.', '.', '.', 'O', /* fmt */
-                '.', 'O', '.', '.', '.', '.', '.', 'O', '#', '.', /* fmt */
-                'O', '.', '#', '.', '.', 'O', '.', '#', '.', '#', /* fmt */
-                '.', '.', 'O', '.', '.', '#', 'O', '.', '.', 'O', /* fmt */
-                '.', '.', '.', '.', '.', '.', '.', 'O', '.', '.', /* fmt */
-                '#', '.', '.', '.', '.', '#', '#', '#', '.', '.', /* fmt */
-                '#', 'O', 'O', '.', '.', '#', '.', '.', '.', '.', /* fmt */
-            ]
-            .to_vec(),
-        };
-
-        let expected_grid = Grid {
-            width: 10,
-            height: 10,
-            data: [
-                '.', '.', '.', '.', 'O', '#', '.', '.', '.', '.', /* fmt */
-                '.', 'O', 'O', 'O', '#', '.', '.', '.', '.', '#', /* fmt */
-                '.', '.', '.', '.', '.', '#', '#', '.', '.', '.', /* fmt */
-                '.', 'O', 'O', '#', '.', '.', '.', '.', 'O', 'O', /* fmt */
-                '.', '.', '.', '.', '.', '.', 'O', 'O', '#', '.', /* fmt */
-                '.', 'O', '#', '.', '.', '.', 'O', '#', '.', '#', /* fmt */
-                '.', '.', '.', '.', 'O', '#', '.', '.', 'O', 'O', /* fmt */
-                '.', '.', '.', '.', '.', '.', '.', '.', '.', 'O', /* fmt */
-                '#', '.', '.', '.', '.', '#', '#', '#', '.', '.', /* fmt */
-                '#', '.', '.', 'O', 'O', '#', '.', '.', '.', '.', /* fmt */
-            ]
-            .to_vec(),
-        };
-
-        assert_eq!(tilt(grid, Direction::EAST).data, expected_grid.data);
+        let grid = grid![
+            "O....#....",
+            "O.OO#....#",
+            ".....##...",
+            "OO.#O....O",
+            ".O.....O#.",
+            "O.#..O.#.#",
+            "..O..#O..O",
+            ".......O..",
+            "#....###..",
+            "#OO..#....",
+        ];
+
+        let expected_grid = grid![
+            "....O#....",
+            ".OOO#....#",
+            ".....##...",
+            ".OO#....OO",
+            "......OO#.",
+            ".O#...O#.#",
+            "....O#..OO",
+            ".........O",
+            "#....###..",
+            "#..OO#....",
+        ];
+
+        assert_eq!(tilt(grid, Direction::RIGHT), expected_grid);
     }
 }
@@ -1,48 +1,49 @@
 use std::vec;
 
-use utils::string::read_string;
+use utils::{
+    error::{solve, ParseError},
+    parse::{alt, integer, map, pair, parse_all, preceded, tag, take_while1},
+    string::read_string,
+};
 
 type SolutionType = usize;
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day 15 - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day 15 - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> SolutionType {
-    read_string(file_name)
+fn part_one(file_name: &str) -> Result<SolutionType, ParseError> {
+    Ok(read_string(file_name)
         .split(",")
         .map(|sequence| hash_string(sequence.trim()))
-        .sum()
+        .sum())
 }
 
-fn part_two(file_name: &str) -> SolutionType {
+fn part_two(file_name: &str) -> Result<SolutionType, ParseError> {
     const V: Vec<(&str, usize)> = vec![];
     let mut boxes = [V; 256];
 
     let content = read_string(file_name);
-    let trimmed = content.trim_end_matches('\n');
-
-    trimmed.split(",").for_each(|s| {
-        if let Some(label) = s.strip_suffix('-') {
+    for (label, focal_length) in parse_steps(content.trim())? {
+        let box_content = &mut boxes[hash_string(label)];
+        match focal_length {
             // remove the label from the box
-            println!("Removing {}", label);
-            boxes[hash_string(label)].retain(|&(l, _)| l != label);
-        } else if let Some((label, focal_length)) = s.split_once("=") {
-            let box_content = &mut boxes[hash_string(label)];
-            let focal_length = focal_length.parse::<usize>().unwrap();
-            if let Some(index) = box_content.iter().position(|&(l, _)| l == label) {
-                box_content[index] = (label, focal_length)
-            } else {
-                box_content.push((label, focal_length));
+            None => box_content.retain(|&(l, _)| l != label),
+            Some(focal_length) => {
+                if let Some(index) = box_content.iter().position(|&(l, _)| l == label) {
+                    box_content[index] = (label, focal_length)
+                } else {
+                    box_content.push((label, focal_length));
+                }
             }
         }
-    });
+    }
 
-    boxes
+    Ok(boxes
         .iter()
         .enumerate()
         .map(|(box_index, foci)| {
@@ -53,7 +54,31 @@ fn part_two(file_name: &str) -> SolutionType {
                     .map(|(index, (_, focal_length))| (index + 1) * focal_length)
                     .sum::<usize>()
         })
-        .sum()
+        .sum())
+}
+
+/**
+ * The comma separated steps as a label with the focal length to set, or None for '-'
+ */
+fn parse_steps(sequence: &str) -> Result<Vec<(&str, Option<usize>)>, ParseError> {
+    let step = pair(
+        take_while1(|c: char| c.is_ascii_lowercase(), "a label"),
+        alt((
+            map(tag("-"), |_| None),
+            map(preceded(tag("="), integer::<usize>()), Some),
+        )),
+    );
+
+    let mut steps = vec![];
+    let mut offset = 0;
+    for text in sequence.split(',') {
+        steps.push(parse_all(&step, text).map_err(|e| {
+            // parse_all only saw this step, so point into the whole line instead
+            ParseError::new(1, offset + e.column, sequence, e.expected)
+        })?);
+        offset += text.chars().count() + 1;
+    }
+    Ok(steps)
 }
 
 fn hash_string(input: &str) -> usize {
@@ -72,12 +97,18 @@ mod test {
 
     #[test]
     fn part_one_test() {
-        assert_eq!(1320, part_one("data/example.txt"));
+        assert_eq!(Ok(1320), part_one("data/example.txt"));
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(145, part_two("data/example.txt"))
+        assert_eq!(Ok(145), part_two("data/example.txt"))
+    }
+
+    #[test]
+    fn crlf_test() {
+        assert_eq!(Ok(1320), part_one("data/example_crlf.txt"));
+        assert_eq!(Ok(145), part_two("data/example_crlf.txt"));
     }
 
     #[test]
@@ -1,21 +1,24 @@
-use utils::string::{read, read_string};
+use utils::{
+    error::{solve, ParseError},
+    string::{read, read_string},
+};
 
 type SolutionType = u32;
 
 fn main() {
-    let part_one_solution = part_one("data/input.txt");
+    let part_one_solution = solve("data/input.txt", part_one);
     println!("Day {{.day}} - Part 1 solution is '{part_one_solution:?}'");
 
-    let part_two_solution = part_two("data/input.txt");
+    let part_two_solution = solve("data/input.txt", part_two);
     println!("Day {{.day}} - Part 2 solution is '{part_two_solution:?}'");
 }
 
-fn part_one(file_name: &str) -> SolutionType {
+fn part_one(file_name: &str) -> Result<SolutionType, ParseError> {
     let input = read(file_name);
     todo!()
 }
 
-fn part_two(file_name: &str) -> SolutionType {
+fn part_two(file_name: &str) -> Result<SolutionType, ParseError> {
     let input = read_string(file_name);
     todo!()
 }
@@ -26,11 +29,11 @@ mod test {
 
     #[test]
     fn part_one_test() {
-        assert_eq!(0, part_one("data/example.txt"))
+        assert_eq!(Ok(0), part_one("data/example.txt"))
     }
 
     #[test]
     fn part_two_test() {
-        assert_eq!(0, part_two("data/example.txt"))
+        assert_eq!(Ok(0), part_two("data/example.txt"))
     }
 }
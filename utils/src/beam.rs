@@ -0,0 +1,264 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+pub use smallvec::{smallvec, SmallVec};
+
+use crate::{
+    grid::Grid,
+    point::{Direction, Point},
+};
+
+/**
+ * The directions a beam leaves a tile in. Most tiles pass a beam on or bend it, splitters emit
+ * two beams and absorbers none, so two entries fit without allocating.
+ */
+pub type Outgoing = SmallVec<[Direction; 2]>;
+
+/**
+ * A beam entering a cell while travelling in a direction
+ */
+pub type BeamState = (Point, Direction);
+
+/**
+ * Traces beams of light through a grid, e.g. the mirrors and splitters of 2023 day 16.
+ *
+ * The behaviour of each tile is given by a function from the tile and the direction the beam
+ * travels in to the directions it continues in, so new tile sets only need a new function:
+ *
+ * ```text
+ * Beam::new(&grid, |&tile, direction| match tile {
+ *     '/' => smallvec![direction.reflect_slash()],
+ *     '#' => smallvec![],
+ *     _ => smallvec![direction],
+ * })
+ * ```
+ *
+ * Portals are added with `with_portals`, which moves a beam to another cell before it continues.
+ */
+pub struct Beam<'a, T, F, P = fn(Point, &T) -> Option<Point>> {
+    grid: &'a Grid<T>,
+    tile: F,
+    portal: Option<P>,
+}
+
+/**
+ * Everything a beam touched while tracing from a start state
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeamTrace {
+    states: HashSet<BeamState>,
+    loops: HashSet<BeamState>,
+}
+
+impl<'a, T, F> Beam<'a, T, F>
+where
+    F: Fn(&T, Direction) -> Outgoing,
+{
+    pub fn new(grid: &'a Grid<T>, tile: F) -> Self {
+        Beam {
+            grid,
+            tile,
+            portal: None,
+        }
+    }
+
+    /**
+     * Lets tiles teleport beams: when the function returns a point for a cell, beams leave from
+     * that point instead of the cell they entered
+     */
+    pub fn with_portals<P>(self, portal: P) -> Beam<'a, T, F, P>
+    where
+        P: Fn(Point, &T) -> Option<Point>,
+    {
+        Beam {
+            grid: self.grid,
+            tile: self.tile,
+            portal: Some(portal),
+        }
+    }
+}
+
+impl<'a, T, F, P> Beam<'a, T, F, P>
+where
+    F: Fn(&T, Direction) -> Outgoing,
+    P: Fn(Point, &T) -> Option<Point>,
+{
+    /**
+     * The in-bounds states a beam moves to after entering the given state
+     */
+    fn successors(&self, (point, direction): BeamState) -> SmallVec<[BeamState; 2]> {
+        let Some(cell) = self.grid.get(point) else {
+            return SmallVec::new();
+        };
+        let exit = self
+            .portal
+            .as_ref()
+            .and_then(|portal| portal(point, cell))
+            .unwrap_or(point);
+
+        (self.tile)(cell, direction)
+            .into_iter()
+            .map(|next| (exit + next.unit(), next))
+            .filter(|&(next, _)| self.grid.contains(next))
+            .collect()
+    }
+
+    /**
+     * Follows the beam and all beams split off from it until each one leaves the grid, is
+     * absorbed or repeats a state. A start outside the grid yields an empty trace.
+     *
+     * A state is reported as a loop when a beam returns to it from one of its own descendants,
+     * meaning the beam would keep cycling forever without the visited check.
+     */
+    pub fn trace(&self, start: BeamState) -> BeamTrace {
+        let mut loops = HashSet::new();
+        if !self.grid.contains(start.0) {
+            return BeamTrace {
+                states: HashSet::new(),
+                loops,
+            };
+        }
+
+        // depth-first, flagging the states on the current path to tell loops from merging beams
+        let mut on_path = HashMap::from([(start, true)]);
+        let mut stack = vec![(start, self.successors(start), 0)];
+
+        while let Some((state, successors, index)) = stack.last_mut() {
+            let Some(&next) = successors.get(*index) else {
+                on_path.insert(*state, false);
+                stack.pop();
+                continue;
+            };
+            *index += 1;
+
+            match on_path.entry(next) {
+                Entry::Occupied(entry) => {
+                    if *entry.get() {
+                        loops.insert(next);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(true);
+                    stack.push((next, self.successors(next), 0));
+                }
+            }
+        }
+
+        BeamTrace {
+            states: on_path.into_keys().collect(),
+            loops,
+        }
+    }
+}
+
+impl BeamTrace {
+    /**
+     * Every cell a beam passed through, regardless of direction
+     */
+    pub fn cells(&self) -> HashSet<Point> {
+        self.states.iter().map(|&(point, _)| point).collect()
+    }
+
+    pub fn states(&self) -> &HashSet<BeamState> {
+        &self.states
+    }
+
+    /**
+     * The states at which a beam closed a cycle, empty when every beam eventually stops
+     */
+    pub fn loops(&self) -> &HashSet<BeamState> {
+        &self.loops
+    }
+
+    pub fn has_loop(&self) -> bool {
+        !self.loops.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        beam::{smallvec, Beam, Outgoing},
+        grid,
+        point::{Direction, Point},
+    };
+
+    fn mirrors(tile: &char, direction: Direction) -> Outgoing {
+        let vertical = matches!(direction, Direction::UP | Direction::DOWN);
+        match tile {
+            '-' if vertical => smallvec![Direction::LEFT, Direction::RIGHT],
+            '|' if !vertical => smallvec![Direction::UP, Direction::DOWN],
+            '/' => smallvec![direction.reflect_slash()],
+            '\\' => smallvec![direction.reflect_backslash()],
+            '#' => smallvec![],
+            _ => smallvec![direction],
+        }
+    }
+
+    #[test]
+    fn straight_test() {
+        let grid = grid![".....", ".....", "....."];
+        let trace = Beam::new(&grid, mirrors).trace((Point::new(0, 1), Direction::RIGHT));
+
+        assert_eq!(5, trace.cells().len());
+        assert_eq!(5, trace.states().len());
+        assert!(!trace.has_loop());
+
+        let outside = Beam::new(&grid, mirrors).trace((Point::new(5, 1), Direction::RIGHT));
+        assert!(outside.cells().is_empty());
+    }
+
+    #[test]
+    fn splitter_and_absorber_test() {
+        let grid = grid!["..#..", "..|..", "....."];
+        let trace = Beam::new(&grid, mirrors).trace((Point::new(0, 1), Direction::RIGHT));
+
+        // the upwards beam is absorbed by the wall, which still counts as visited
+        assert!(trace.cells().contains(&Point::new(2, 0)));
+        assert_eq!(5, trace.cells().len());
+        assert!(!trace.has_loop());
+    }
+
+    #[test]
+    fn loop_test() {
+        let grid = grid!["/.\\", "...", "\\./"];
+        let trace = Beam::new(&grid, mirrors).trace((Point::new(1, 0), Direction::RIGHT));
+
+        assert_eq!(8, trace.cells().len());
+        assert!(trace
+            .loops()
+            .contains(&(Point::new(1, 0), Direction::RIGHT)));
+
+        // two beams merging into the same path is not a loop
+        let arrows = |&tile: &char, direction| -> Outgoing {
+            match tile {
+                'x' => smallvec![Direction::DOWN, Direction::RIGHT],
+                'd' => smallvec![Direction::DOWN],
+                'r' => smallvec![Direction::RIGHT],
+                _ => smallvec![direction],
+            }
+        };
+        let grid = grid!["xd.", "rr."];
+        let trace = Beam::new(&grid, arrows).trace((Point::new(0, 0), Direction::DOWN));
+        assert_eq!(5, trace.cells().len());
+        assert_eq!(6, trace.states().len());
+        assert!(!trace.has_loop());
+    }
+
+    #[test]
+    fn portal_test() {
+        let grid = grid!["a...", "....", "...a"];
+        let portals = |point: Point, &tile: &char| match (tile, point) {
+            ('a', Point { x: 0, y: 0 }) => Some(Point::new(3, 2)),
+            ('a', _) => Some(Point::new(0, 0)),
+            _ => None,
+        };
+        let trace = Beam::new(&grid, mirrors)
+            .with_portals(portals)
+            .trace((Point::new(0, 0), Direction::LEFT));
+
+        // the beam enters the portal heading left and re-emerges next to the other end
+        assert_eq!(4, trace.cells().len());
+        assert!(!trace.cells().contains(&Point::new(3, 2)));
+        assert!(trace.cells().contains(&Point::new(0, 2)));
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+    fmt::{self, Display},
+    process,
+};
+
+/**
+ * The number of characters after the error position shown in messages
+ */
+const FOUND_LENGTH: usize = 20;
+
+/**
+ * Malformed puzzle input, pointing at where the problem is
+ */
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    /**
+     * Counted from 1
+     */
+    pub line: usize,
+    /**
+     * Counted from 1, in characters
+     */
+    pub column: usize,
+    /**
+     * The full text of the offending line
+     */
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        snippet: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        ParseError {
+            line,
+            column,
+            snippet: snippet.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /**
+     * Moves the error down by the given number of lines, for parsers that only saw part of a file
+     */
+    pub fn offset_lines(self, lines: usize) -> Self {
+        ParseError {
+            line: self.line + lines,
+            ..self
+        }
+    }
+
+    /**
+     * The rest of the snippet from the error position on
+     */
+    pub fn found(&self) -> &str {
+        self.snippet
+            .char_indices()
+            .nth(self.column.saturating_sub(1))
+            .map_or("", |(index, _)| &self.snippet[index..])
+    }
+
+    /**
+     * A compiler style diagnostic, showing the line with a marker under the error position
+     */
+    pub fn report(&self, file_name: &str) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        format!(
+            "error: expected {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}^",
+            self.expected,
+            gutter,
+            file_name,
+            self.line,
+            self.column,
+            gutter,
+            number,
+            self.snippet,
+            gutter,
+            " ".repeat(self.column.saturating_sub(1))
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        match self.found() {
+            "" => write!(f, "end of line"),
+            found => write!(
+                f,
+                "'{}'",
+                found.chars().take(FOUND_LENGTH).collect::<String>()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/**
+ * Why a part could not be solved: the input is malformed, or it is well formed but has no answer
+ */
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SolveError {
+    Parse(ParseError),
+    /**
+     * The reason there is no answer, such as a walk that never reaches its destination
+     */
+    NoSolution(String),
+}
+
+impl SolveError {
+    pub fn no_solution(reason: impl Into<String>) -> Self {
+        SolveError::NoSolution(reason.into())
+    }
+
+    /**
+     * A compiler style diagnostic, see ParseError::report. Inputs without an answer have no
+     * position to point at, so only the file is named.
+     */
+    pub fn report(&self, file_name: &str) -> String {
+        match self {
+            SolveError::Parse(error) => error.report(file_name),
+            SolveError::NoSolution(reason) => {
+                format!("error: no solution, {}\n --> {}", reason, file_name)
+            }
+        }
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> Self {
+        SolveError::Parse(error)
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Parse(error) => write!(f, "{}", error),
+            SolveError::NoSolution(reason) => write!(f, "no solution, {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/**
+ * Runs one part of a day on the file. When the input is malformed or has no answer this prints a
+ * diagnostic and exits, rather than panicking with a backtrace.
+ */
+pub fn solve<T, E: Into<SolveError>>(
+    file_name: &str,
+    part: impl FnOnce(&str) -> Result<T, E>,
+) -> T {
+    try_solve(file_name, part).unwrap_or_else(|report| {
+        eprintln!("{}", report);
+        process::exit(1)
+    })
+}
+
+/**
+ * The solution, or the diagnostic solve prints
+ */
+fn try_solve<T, E: Into<SolveError>>(
+    file_name: &str,
+    part: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<T, String> {
+    part(file_name).map_err(|error| error.into().report(file_name))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::{solve, try_solve, ParseError, SolveError};
+
+    #[test]
+    fn display_test() {
+        let error = ParseError::new(3, 11, "Game 3: 8 purple, 2 red", "'red' or 'blue'");
+        assert_eq!("purple, 2 red", error.found());
+        assert_eq!(
+            "line 3, column 11: expected 'red' or 'blue', found 'purple, 2 red'",
+            error.to_string()
+        );
+
+        let error = ParseError::new(1, 4, "abc", "a digit").offset_lines(4);
+        assert_eq!(5, error.line);
+        assert_eq!(
+            "line 5, column 4: expected a digit, found end of line",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn report_test() {
+        let error = ParseError::new(12, 3, "1 x 3", "an integer");
+        assert_eq!(
+            "error: expected an integer\n  --> data/input.txt:12:3\n   |\n12 | 1 x 3\n   |   ^",
+            error.report("data/input.txt")
+        );
+    }
+
+    #[test]
+    fn solve_test() {
+        let part = |file_name: &str| -> Result<usize, ParseError> {
+            let line = "Game 1: 3 blue";
+            match file_name {
+                "data/example.txt" => Ok(line.len()),
+                _ => Err(ParseError::new(2, 8, line, "':'")),
+            }
+        };
+
+        assert_eq!(14, solve("data/example.txt", part));
+        assert_eq!(
+            Err("error: expected ':'\n --> data/input.txt:2:8\n  |\n2 | Game 1: 3 blue\n  |        ^"
+                .to_owned()),
+            try_solve("data/input.txt", part)
+        );
+
+        let part = |_: &str| -> Result<usize, SolveError> {
+            Err(SolveError::no_solution("the walk never ends"))
+        };
+        assert_eq!(
+            Err("error: no solution, the walk never ends\n --> data/input.txt".to_owned()),
+            try_solve("data/input.txt", part)
+        );
+    }
+}
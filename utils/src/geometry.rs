@@ -0,0 +1,7 @@
+pub mod polygon;
+pub mod rect;
+pub mod segment;
+
+pub use polygon::Polygon;
+pub use rect::Rect;
+pub use segment::{Bresenham, Intersection, RationalPoint, Ray, Segment};
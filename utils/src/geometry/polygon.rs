@@ -0,0 +1,226 @@
+use crate::{
+    geometry::Segment,
+    num::gcd,
+    point::{Direction, Point, ORIGIN},
+};
+
+/**
+ * A simple polygon on the integer lattice, given by its vertices in order.
+ * The last vertex is implicitly connected back to the first.
+ *
+ * The area uses the Shoelace formula (https://en.wikipedia.org/wiki/Shoelace_formula) and the
+ * lattice point counts use Pick's Theorem (https://en.wikipedia.org/wiki/Pick%27s_theorem):
+ *      A = i + b/2 - 1 -> i = A - b/2 + 1
+ */
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point>) -> Self {
+        Polygon { vertices }
+    }
+
+    /**
+     * Traces a polygon from the origin by following run-length instructions such as a dig plan.
+     * A final instruction leading back to the origin does not produce a duplicate vertex.
+     */
+    pub fn from_instructions<I>(instructions: I) -> Self
+    where
+        I: IntoIterator<Item = (Direction, i64)>,
+    {
+        let mut vertices = vec![ORIGIN];
+        let mut current = ORIGIN;
+
+        for (direction, length) in instructions {
+            current += direction.unit() * length;
+            vertices.push(current);
+        }
+
+        if vertices.len() > 1 && vertices.last() == vertices.first() {
+            vertices.pop();
+        }
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+
+    /**
+     * Twice the enclosed area, which is always an integer for lattice polygons
+     */
+    pub fn twice_area(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128)
+            .sum::<i128>()
+            .abs()
+    }
+
+    /**
+     * The enclosed area, rounded down. This is exact for polygons whose edges follow grid lines.
+     */
+    pub fn area(&self) -> i128 {
+        self.twice_area() / 2
+    }
+
+    /**
+     * The length of the boundary, following diagonal edges in a straight line
+     */
+    pub fn perimeter(&self) -> f64 {
+        self.edges()
+            .map(|(a, b)| ((b.x - a.x) as f64).hypot((b.y - a.y) as f64))
+            .sum()
+    }
+
+    /**
+     * The number of lattice points on the boundary, vertices included
+     */
+    pub fn boundary_points(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| {
+                let (dx, dy) = ((b.x - a.x).unsigned_abs(), (b.y - a.y).unsigned_abs());
+                gcd(dx as u128, dy as u128) as i128
+            })
+            .sum()
+    }
+
+    /**
+     * The number of lattice points strictly inside the polygon, which is 0 when it encloses no
+     * area, since Pick's Theorem only holds for proper polygons
+     */
+    pub fn interior_points(&self) -> i128 {
+        match self.twice_area() {
+            0 => 0,
+            twice_area => (twice_area - self.boundary_points() + 2) / 2,
+        }
+    }
+
+    /**
+     * Whether the point lies on one of the edges
+     */
+    pub fn on_boundary(&self, point: Point) -> bool {
+        self.edges()
+            .any(|(a, b)| Segment::new(a, b).contains(point))
+    }
+
+    /**
+     * Whether the point lies inside the polygon or on its boundary
+     */
+    pub fn contains(&self, point: Point) -> bool {
+        if self.on_boundary(point) {
+            return true;
+        }
+
+        // cast a ray towards positive x and count how many edges it crosses
+        self.edges()
+            .filter(|&(a, b)| (a.y > point.y) != (b.y > point.y))
+            .filter(|&(a, b)| {
+                let lhs = (point.x - a.x) as i128 * (b.y - a.y) as i128;
+                let rhs = (point.y - a.y) as i128 * (b.x - a.x) as i128;
+                if b.y > a.y {
+                    lhs < rhs
+                } else {
+                    lhs > rhs
+                }
+            })
+            .count()
+            % 2
+            == 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        geometry::Polygon,
+        point::{Direction, Point},
+    };
+
+    #[test]
+    fn square_test() {
+        let square = Polygon::from_instructions([
+            (Direction::RIGHT, 2),
+            (Direction::DOWN, 2),
+            (Direction::LEFT, 2),
+            (Direction::UP, 2),
+        ]);
+
+        assert_eq!(4, square.vertices().len());
+        assert_eq!(4, square.area());
+        assert_eq!(8.0, square.perimeter());
+        assert_eq!(8, square.boundary_points());
+        assert_eq!(1, square.interior_points());
+    }
+
+    #[test]
+    fn dig_plan_test() {
+        let plan = "R 6, D 5, L 2, D 2, R 2, D 2, L 5, U 2, L 1, U 2, R 2, U 3, L 2, U 2";
+        let lagoon = Polygon::from_instructions(plan.split(", ").map(|step| {
+            let (direction, length) = step.split_once(' ').unwrap();
+            (direction.parse().unwrap(), length.parse().unwrap())
+        }));
+
+        assert_eq!(38, lagoon.boundary_points());
+        assert_eq!(62, lagoon.interior_points() + lagoon.boundary_points());
+    }
+
+    #[test]
+    fn diagonal_test() {
+        let triangle = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0), Point::new(0, 3)]);
+
+        assert_eq!(12, triangle.twice_area());
+        assert_eq!(6, triangle.area());
+        assert_eq!(8, triangle.boundary_points());
+        assert_eq!(3, triangle.interior_points());
+        assert_eq!(12.0, triangle.perimeter());
+    }
+
+    #[test]
+    fn degenerate_test() {
+        let empty = Polygon::new(vec![]);
+        assert_eq!(0, empty.twice_area());
+        assert_eq!(0, empty.interior_points());
+
+        let line = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0), Point::new(2, 0)]);
+        assert_eq!(0, line.twice_area());
+        assert_eq!(8, line.boundary_points());
+        assert_eq!(0, line.interior_points());
+    }
+
+    #[test]
+    fn contains_test() {
+        // a U shape, open towards the top
+        let shape = Polygon::new(vec![
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(1, 2),
+            Point::new(2, 2),
+            Point::new(2, 0),
+            Point::new(3, 0),
+            Point::new(3, 3),
+            Point::new(0, 3),
+        ]);
+
+        assert!(shape.contains(Point::new(0, 0)));
+        assert!(shape.on_boundary(Point::new(1, 1)));
+        assert!(shape.contains(Point::new(3, 2)));
+        assert!(!shape.contains(Point::new(4, 2)));
+        assert!(!shape.on_boundary(Point::new(4, 2)));
+        assert!(!shape.contains(Point::new(-1, 1)));
+        assert_eq!(7, shape.area());
+
+        let triangle = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0), Point::new(0, 4)]);
+        assert!(triangle.contains(Point::new(1, 1)));
+        assert!(triangle.contains(Point::new(2, 2)));
+        assert!(!triangle.contains(Point::new(3, 2)));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::point::Point;
+
+/**
+ * An axis-aligned rectangle of lattice points, with both corners included.
+ * A rectangle whose max lies before its min in either axis is empty.
+ */
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    pub const fn new(min: Point, max: Point) -> Self {
+        Rect { min, max }
+    }
+
+    /**
+     * The rectangle spanned by any two opposite corners
+     */
+    pub fn from_corners(a: Point, b: Point) -> Self {
+        Rect::new(
+            Point::new(a.x.min(b.x), a.y.min(b.y)),
+            Point::new(a.x.max(b.x), a.y.max(b.y)),
+        )
+    }
+
+    /**
+     * The smallest rectangle containing every point, or None when there are no points
+     */
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Rect::new(first, first), |rect, point| rect.include(point)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max.x < self.min.x || self.max.y < self.min.y
+    }
+
+    pub fn width(&self) -> i64 {
+        (self.max.x - self.min.x + 1).max(0)
+    }
+
+    pub fn height(&self) -> i64 {
+        (self.max.y - self.min.y + 1).max(0)
+    }
+
+    /**
+     * The number of lattice points in the rectangle
+     */
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+    }
+
+    /**
+     * The overlapping region, or None if the rectangles are disjoint
+     */
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect::new(
+            Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        );
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /**
+     * The smallest rectangle containing both rectangles
+     */
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        Rect::new(
+            Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    /**
+     * Grows the rectangle just enough to contain the point, e.g. to track the extent of a sparse grid
+     */
+    pub fn include(&self, point: Point) -> Rect {
+        self.union(&Rect::new(point, point))
+    }
+
+    /**
+     * Grows the rectangle by the given amount on every side
+     */
+    pub fn expand(&self, amount: u32) -> Rect {
+        let offset = Point::new(amount as i64, amount as i64);
+        Rect::new(self.min - offset, self.max + offset)
+    }
+
+    /**
+     * Shrinks the rectangle by the given amount on every side, or None if nothing is left
+     */
+    pub fn shrink(&self, amount: u32) -> Option<Rect> {
+        let offset = Point::new(amount as i64, amount as i64);
+        let rect = Rect::new(self.min + offset, self.max - offset);
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /**
+     * The point inside the rectangle closest to the given point.
+     * Panics when the rectangle is empty.
+     */
+    pub fn clamp(&self, point: Point) -> Point {
+        assert!(
+            !self.is_empty(),
+            "Attempted to clamp to an empty {:?}",
+            self
+        );
+        Point::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+        )
+    }
+
+    /**
+     * All points in row-major order
+     */
+    pub fn iter_points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{geometry::Rect, point::Point};
+
+    #[test]
+    fn from_points_test() {
+        let rect = Rect::from_points([Point::new(3, -1), Point::new(-2, 4), Point::new(0, 0)]);
+
+        assert_eq!(Some(Rect::new(Point::new(-2, -1), Point::new(3, 4))), rect);
+        assert_eq!(None, Rect::from_points([]));
+        assert_eq!(
+            rect.unwrap(),
+            Rect::from_corners(Point::new(3, 4), Point::new(-2, -1))
+        );
+    }
+
+    #[test]
+    fn size_test() {
+        let rect = Rect::new(Point::new(0, 0), Point::new(2, 1));
+
+        assert_eq!((3, 2, 6), (rect.width(), rect.height(), rect.area()));
+        assert_eq!(6, rect.iter_points().count());
+        assert_eq!(Some(Point::new(2, 0)), rect.iter_points().nth(2));
+        assert!(rect.contains(Point::new(2, 1)));
+        assert!(!rect.contains(Point::new(3, 1)));
+
+        let empty = Rect::new(Point::new(0, 0), Point::new(-1, 5));
+        assert!(empty.is_empty());
+        assert_eq!(0, empty.area());
+        assert_eq!(0, empty.iter_points().count());
+    }
+
+    #[test]
+    fn combine_test() {
+        let a = Rect::new(Point::new(0, 0), Point::new(4, 4));
+        let b = Rect::new(Point::new(3, 2), Point::new(6, 9));
+        let c = Rect::new(Point::new(5, 5), Point::new(6, 6));
+
+        assert_eq!(
+            Some(Rect::new(Point::new(3, 2), Point::new(4, 4))),
+            a.intersection(&b)
+        );
+        assert_eq!(None, a.intersection(&c));
+        assert_eq!(Rect::new(Point::new(0, 0), Point::new(6, 9)), a.union(&b));
+        assert_eq!(
+            Rect::new(Point::new(0, -1), Point::new(4, 4)),
+            a.include(Point::new(2, -1))
+        );
+    }
+
+    #[test]
+    fn resize_test() {
+        let rect = Rect::new(Point::new(0, 0), Point::new(4, 2));
+
+        assert_eq!(
+            Rect::new(Point::new(-1, -1), Point::new(5, 3)),
+            rect.expand(1)
+        );
+        assert_eq!(
+            Some(Rect::new(Point::new(1, 1), Point::new(3, 1))),
+            rect.shrink(1)
+        );
+        assert_eq!(None, rect.shrink(2));
+        assert_eq!(Point::new(4, 0), rect.clamp(Point::new(10, -3)));
+        assert_eq!(Point::new(2, 1), rect.clamp(Point::new(2, 1)));
+    }
+}
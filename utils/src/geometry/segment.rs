@@ -0,0 +1,453 @@
+use crate::{
+    num::{gcd, Rational},
+    point::Point,
+};
+
+/**
+ * A point with rational coordinates x / denominator and y / denominator.
+ * The fraction is kept normalised: the denominator is positive and shares no factor with both
+ * numerators, so equal points compare equal.
+ */
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RationalPoint {
+    x: i128,
+    y: i128,
+    denominator: i128,
+}
+
+impl RationalPoint {
+    pub fn new(x: i128, y: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "RationalPoint denominator cannot be zero");
+
+        let sign = denominator.signum();
+        let divisor = gcd(
+            gcd(x.unsigned_abs(), y.unsigned_abs()),
+            denominator.unsigned_abs(),
+        ) as i128;
+
+        RationalPoint {
+            x: sign * x / divisor,
+            y: sign * y / divisor,
+            denominator: sign * denominator / divisor,
+        }
+    }
+
+    pub fn numerators(&self) -> (i128, i128) {
+        (self.x, self.y)
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn x(&self) -> Rational {
+        Rational::new(self.x, self.denominator)
+    }
+
+    pub fn y(&self) -> Rational {
+        Rational::new(self.y, self.denominator)
+    }
+
+    /**
+     * Returns the lattice point if both coordinates are integers
+     */
+    pub fn to_point(self) -> Option<Point> {
+        if self.denominator == 1 {
+            Some(Point::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<Point> for RationalPoint {
+    fn from(point: Point) -> Self {
+        RationalPoint::new(point.x as i128, point.y as i128, 1)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Intersection {
+    Point(RationalPoint),
+    Overlap(Segment),
+}
+
+/**
+ * A closed line segment between two lattice points
+ */
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+fn cross(a: Point, b: Point) -> i128 {
+    a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128
+}
+
+fn dot(a: Point, b: Point) -> i128 {
+    a.x as i128 * b.x as i128 + a.y as i128 * b.y as i128
+}
+
+/**
+ * Whether numerator / denominator lies within [0, 1], without dividing
+ */
+fn unit_interval(numerator: i128, denominator: i128) -> bool {
+    if denominator > 0 {
+        0 <= numerator && numerator <= denominator
+    } else {
+        denominator <= numerator && numerator <= 0
+    }
+}
+
+impl Segment {
+    pub const fn new(start: Point, end: Point) -> Self {
+        Segment { start, end }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    pub fn is_axis_aligned(&self) -> bool {
+        self.is_horizontal() || self.is_vertical()
+    }
+
+    /**
+     * All lattice points along the segment using Bresenham's line algorithm, both ends included.
+     * Horizontal, vertical and 45 degree segments yield exactly the points they pass through.
+     */
+    pub fn points(&self) -> Bresenham {
+        Bresenham::new(self.start, self.end)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        cross(self.end - self.start, point - self.start) == 0
+            && self.start.x.min(self.end.x) <= point.x
+            && point.x <= self.start.x.max(self.end.x)
+            && self.start.y.min(self.end.y) <= point.y
+            && point.y <= self.start.y.max(self.end.y)
+    }
+
+    /**
+     * The shared part of two collinear segments, e.g. two horizontal segments on the same row.
+     * Returns None for segments that are not collinear or do not touch.
+     */
+    pub fn overlap(&self, other: &Segment) -> Option<Segment> {
+        let direction = self.end - self.start;
+        if direction == Point::new(0, 0) {
+            return other
+                .contains(self.start)
+                .then_some(Segment::new(self.start, self.start));
+        }
+        if cross(direction, other.start - self.start) != 0
+            || cross(direction, other.end - self.start) != 0
+        {
+            return None;
+        }
+
+        let position = |p: Point| dot(p - self.start, direction);
+        let ordered = |s: &Segment| {
+            if position(s.start) <= position(s.end) {
+                (s.start, s.end)
+            } else {
+                (s.end, s.start)
+            }
+        };
+
+        let (a_start, a_end) = ordered(self);
+        let (b_start, b_end) = ordered(other);
+        let start = if position(a_start) >= position(b_start) {
+            a_start
+        } else {
+            b_start
+        };
+        let end = if position(a_end) <= position(b_end) {
+            a_end
+        } else {
+            b_end
+        };
+
+        (position(start) <= position(end)).then_some(Segment::new(start, end))
+    }
+
+    /**
+     * The exact intersection of two segments: a single (possibly fractional) point when they cross
+     * or touch, or the shared segment when they are collinear and overlap.
+     */
+    pub fn intersection(&self, other: &Segment) -> Option<Intersection> {
+        let r = self.end - self.start;
+        let s = other.end - other.start;
+        let denominator = cross(r, s);
+        let offset = other.start - self.start;
+
+        if denominator == 0 {
+            return self.overlap(other).map(|overlap| {
+                if overlap.start == overlap.end {
+                    Intersection::Point(overlap.start.into())
+                } else {
+                    Intersection::Overlap(overlap)
+                }
+            });
+        }
+
+        let t = cross(offset, s);
+        let u = cross(offset, r);
+        if !unit_interval(t, denominator) || !unit_interval(u, denominator) {
+            return None;
+        }
+
+        Some(Intersection::Point(RationalPoint::new(
+            self.start.x as i128 * denominator + t * r.x as i128,
+            self.start.y as i128 * denominator + t * r.y as i128,
+            denominator,
+        )))
+    }
+}
+
+/**
+ * Iterator over the lattice points of a segment, see Segment::points
+ */
+#[derive(Clone, Debug)]
+pub struct Bresenham {
+    current: Point,
+    end: Point,
+    delta: Point,
+    step: Point,
+    error: i64,
+    done: bool,
+}
+
+impl Bresenham {
+    fn new(start: Point, end: Point) -> Self {
+        let delta = Point::new((end.x - start.x).abs(), -(end.y - start.y).abs());
+        Bresenham {
+            current: start,
+            end,
+            delta,
+            step: (end - start).signum(),
+            error: delta.x + delta.y,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Bresenham {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let point = self.current;
+        if point == self.end {
+            self.done = true;
+        } else {
+            let doubled_error = 2 * self.error;
+            if doubled_error >= self.delta.y {
+                self.error += self.delta.y;
+                self.current.x += self.step.x;
+            }
+            if doubled_error <= self.delta.x {
+                self.error += self.delta.x;
+                self.current.y += self.step.y;
+            }
+        }
+        Some(point)
+    }
+}
+
+/**
+ * A half-line origin + t * direction for t >= 0, e.g. the path of a hailstone
+ */
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Ray {
+    pub origin: Point,
+    pub direction: Point,
+}
+
+impl Ray {
+    pub const fn new(origin: Point, direction: Point) -> Self {
+        Ray { origin, direction }
+    }
+
+    /**
+     * Where the infinite lines through both rays cross.
+     * Parallel lines, including coincident ones, have no single crossing and return None.
+     */
+    pub fn line_intersection(&self, other: &Ray) -> Option<RationalPoint> {
+        self.parameters(other)
+            .map(|(t, _, denominator)| self.at(t, denominator))
+    }
+
+    /**
+     * Where both rays cross, only looking forwards from each origin
+     */
+    pub fn intersection(&self, other: &Ray) -> Option<RationalPoint> {
+        let (t, u, denominator) = self.parameters(other)?;
+        // both parameters share the sign of the denominator when they are non-negative
+        let forwards = |v: i128| v == 0 || v.signum() == denominator.signum();
+
+        (forwards(t) && forwards(u)).then(|| self.at(t, denominator))
+    }
+
+    fn parameters(&self, other: &Ray) -> Option<(i128, i128, i128)> {
+        let denominator = cross(self.direction, other.direction);
+        if denominator == 0 {
+            return None;
+        }
+        let offset = other.origin - self.origin;
+        Some((
+            cross(offset, other.direction),
+            cross(offset, self.direction),
+            denominator,
+        ))
+    }
+
+    fn at(&self, t: i128, denominator: i128) -> RationalPoint {
+        RationalPoint::new(
+            self.origin.x as i128 * denominator + t * self.direction.x as i128,
+            self.origin.y as i128 * denominator + t * self.direction.y as i128,
+            denominator,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        geometry::{Intersection, RationalPoint, Ray, Segment},
+        num::Rational,
+        point::Point,
+    };
+
+    #[test]
+    fn rasterize_test() {
+        let horizontal = Segment::new(Point::new(3, 4), Point::new(0, 4));
+        assert_eq!(
+            vec![
+                Point::new(3, 4),
+                Point::new(2, 4),
+                Point::new(1, 4),
+                Point::new(0, 4)
+            ],
+            horizontal.points().collect::<Vec<_>>()
+        );
+
+        let diagonal = Segment::new(Point::new(9, 7), Point::new(7, 9));
+        assert_eq!(
+            vec![Point::new(9, 7), Point::new(8, 8), Point::new(7, 9)],
+            diagonal.points().collect::<Vec<_>>()
+        );
+
+        let steep = Segment::new(Point::new(0, 0), Point::new(2, 5));
+        let points = steep.points().collect::<Vec<_>>();
+        assert_eq!(6, points.len());
+        assert_eq!(Some(&Point::new(2, 5)), points.last());
+        assert!(points.windows(2).all(|w| w[0].chebyshev(w[1]) == 1));
+
+        let single = Segment::new(Point::new(1, 1), Point::new(1, 1));
+        assert_eq!(1, single.points().count());
+    }
+
+    #[test]
+    fn overlap_test() {
+        let a = Segment::new(Point::new(0, 9), Point::new(5, 9));
+        let b = Segment::new(Point::new(7, 9), Point::new(3, 9));
+        assert_eq!(
+            Some(Segment::new(Point::new(3, 9), Point::new(5, 9))),
+            a.overlap(&b)
+        );
+
+        let c = Segment::new(Point::new(6, 9), Point::new(8, 9));
+        assert_eq!(None, a.overlap(&c));
+
+        // the squared length of these overflows an i64
+        let large = 3_000_000_000_000;
+        let e = Segment::new(Point::new(0, 0), Point::new(2 * large, 2 * large));
+        let f = Segment::new(Point::new(3 * large, 3 * large), Point::new(large, large));
+        assert_eq!(
+            Some(Segment::new(
+                Point::new(large, large),
+                Point::new(2 * large, 2 * large)
+            )),
+            e.overlap(&f)
+        );
+
+        let d = Segment::new(Point::new(0, 8), Point::new(5, 8));
+        assert_eq!(None, a.overlap(&d));
+
+        let touching = Segment::new(Point::new(5, 9), Point::new(5, 20));
+        assert_eq!(
+            Some(Intersection::Point(Point::new(5, 9).into())),
+            a.intersection(&touching)
+        );
+    }
+
+    #[test]
+    fn intersection_test() {
+        let a = Segment::new(Point::new(0, 0), Point::new(4, 4));
+        let b = Segment::new(Point::new(0, 4), Point::new(4, 0));
+        assert_eq!(
+            Some(Intersection::Point(Point::new(2, 2).into())),
+            a.intersection(&b)
+        );
+
+        let c = Segment::new(Point::new(0, 1), Point::new(1, 0));
+        assert_eq!(
+            Some(Intersection::Point(RationalPoint::new(1, 1, 2))),
+            a.intersection(&c)
+        );
+
+        let d = Segment::new(Point::new(5, 0), Point::new(9, 0));
+        assert_eq!(None, a.intersection(&d));
+
+        let e = Segment::new(Point::new(2, 2), Point::new(6, 6));
+        assert_eq!(
+            Some(Intersection::Overlap(Segment::new(
+                Point::new(2, 2),
+                Point::new(4, 4)
+            ))),
+            a.intersection(&e)
+        );
+    }
+
+    #[test]
+    fn rational_point_test() {
+        let point = RationalPoint::new(-4, 6, -4);
+        assert_eq!((2, -3), point.numerators());
+        assert_eq!(2, point.denominator());
+        assert_eq!(
+            (Rational::from(1), Rational::new(-3, 2)),
+            (point.x(), point.y())
+        );
+        assert_eq!(None, point.to_point());
+        assert_eq!(
+            Some(Point::new(1, 2)),
+            RationalPoint::new(3, 6, 3).to_point()
+        );
+    }
+
+    #[test]
+    fn ray_test() {
+        // hailstones A and B from the 2023 day 24 example
+        let a = Ray::new(Point::new(19, 13), Point::new(-2, 1));
+        let b = Ray::new(Point::new(18, 19), Point::new(-1, -1));
+        let crossing = a.intersection(&b).unwrap();
+        assert_eq!((43, 46), crossing.numerators());
+        assert_eq!(3, crossing.denominator());
+
+        // hailstone C crosses A's line in A's past
+        let c = Ray::new(Point::new(20, 19), Point::new(1, -5));
+        assert!(a.line_intersection(&c).is_some());
+        assert_eq!(None, a.intersection(&c));
+
+        let parallel = Ray::new(Point::new(0, 0), Point::new(-4, 2));
+        assert_eq!(None, a.line_intersection(&parallel));
+    }
+}
@@ -1,15 +1,90 @@
-use std::fmt::Debug;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Debug, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::{error::ParseError, geometry::Rect, num::Integer, point::Point};
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Grid<T> {
-    pub width: usize,
-    pub height: usize,
-    pub data: Vec<T>,
+    width: usize,
+    height: usize,
+    data: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseGridError {
+    Empty,
+    UnevenRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "cannot parse a grid from empty input"),
+            ParseGridError::UnevenRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} cells, expected {} like the first row",
+                row, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl ParseGridError {
+    /**
+     * The error as a ParseError pointing into the text the grid was parsed from
+     */
+    pub fn locate(&self, input: &str) -> ParseError {
+        match self {
+            ParseGridError::Empty => ParseError::new(1, 1, "", "at least one row"),
+            ParseGridError::UnevenRow {
+                row,
+                expected,
+                found,
+            } => ParseError::new(
+                row + 1,
+                expected.min(found) + 1,
+                input.lines().nth(*row).unwrap_or(""),
+                format!("a row of {} cells", expected),
+            ),
+        }
+    }
+}
+
+/**
+ * Builds a grid from a list of rows. String literals produce a `Grid<char>`:
+ *
+ * ```text
+ * grid!["#.", ".#"]
+ * grid![[1, 2], [3, 4]]
+ * ```
+ */
+#[macro_export]
+macro_rules! grid {
+    ($([$($cell:expr),* $(,)?]),+ $(,)?) => {
+        $crate::grid::Grid::from_rows(vec![$(vec![$($cell),*]),+])
+    };
+    ($($row:literal),+ $(,)?) => {
+        $crate::grid::Grid::from_rows(vec![$($row.chars().collect::<Vec<char>>()),+])
+    };
 }
 
 fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>>
 where
-    T: Clone + Debug,
+    T: Clone,
 {
     assert!(!v.is_empty());
     (0..v[0].len())
@@ -19,34 +94,226 @@ where
 
 impl Grid<char> {
     pub fn parse(input: &str) -> Self {
-        let raw = input
+        input
+            .parse()
+            .unwrap_or_else(|e| panic!("failed to parse grid: {}", e))
+    }
+
+    /**
+     * Parses the grid, reporting the first cell that is not one of the given tiles
+     */
+    pub fn parse_tiles(input: &str, tiles: &str) -> Result<Self, ParseError> {
+        for (y, line) in input.lines().enumerate() {
+            if let Some(x) = line.chars().position(|c| !tiles.contains(c)) {
+                let expected = format!("one of the tiles '{}'", tiles);
+                return Err(ParseError::new(y + 1, x + 1, line, expected));
+            }
+        }
+        input.parse().map_err(|e: ParseGridError| e.locate(input))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let rows = input
             .lines()
             .map(|l| l.chars().collect::<Vec<_>>())
             .collect::<Vec<_>>();
-        let width = raw[0].len();
-        let height = raw.len();
-        let flat = raw.iter().flatten().map(|&c| c).collect::<Vec<_>>();
 
+        Grid::try_from_rows(rows)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, element: T) -> Self {
         Grid {
             width,
             height,
-            data: flat,
+            data: vec![element; width * height],
         }
     }
 
-    pub fn from_rows(rows: Vec<Vec<char>>) -> Self {
-        let width = rows.len();
-        let height = rows[0].len();
+    pub fn from_cols(columns: Vec<Vec<T>>) -> Self {
+        Grid::from_rows(transpose(columns))
+    }
+}
 
+impl<T> Grid<T> {
+    /**
+     * Wraps row-major data in a grid. Panics if the data does not fill the grid exactly.
+     */
+    pub fn from_vec(width: usize, height: usize, data: Vec<T>) -> Self {
+        assert_eq!(
+            width * height,
+            data.len(),
+            "Attempted to create a {}x{} grid from {} elements",
+            width,
+            height,
+            data.len()
+        );
         Grid {
             width,
             height,
-            data: rows.iter().flatten().map(|&c| c).collect::<Vec<_>>(),
+            data,
         }
     }
 
-    pub fn from_cols(columns: Vec<Vec<char>>) -> Self {
-        Grid::from_rows(transpose(columns))
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        Grid::try_from_rows(rows).unwrap_or_else(|e| panic!("failed to build grid: {}", e))
+    }
+
+    fn try_from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseGridError> {
+        let width = rows.first().ok_or(ParseGridError::Empty)?.len();
+        let height = rows.len();
+
+        if let Some((row, found)) = rows
+            .iter()
+            .map(|r| r.len())
+            .enumerate()
+            .find(|&(_, len)| len != width)
+        {
+            return Err(ParseGridError::UnevenRow {
+                row,
+                expected: width,
+                found,
+            });
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            data: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /**
+     * The cells in row-major order
+     */
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /**
+     * Iterates all cells in row-major order along with their coordinate
+     */
+    pub fn iter_points(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.data.iter().enumerate().map(|(index, cell)| {
+            (
+                Point::new((index % self.width) as i64, (index / self.width) as i64),
+                cell,
+            )
+        })
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    fn offset<C: Integer>(&self, point: Point<C>) -> Option<usize> {
+        let (x, y) = point.to_index()?;
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /**
+     * The rectangle covering every cell, from (0, 0) to (width - 1, height - 1)
+     */
+    pub fn bounds(&self) -> Rect {
+        Rect::new(
+            Point::new(0, 0),
+            Point::from((self.width, self.height)) - Point::new(1, 1),
+        )
+    }
+
+    pub fn contains<C: Integer>(&self, point: Point<C>) -> bool {
+        self.offset(point).is_some()
+    }
+
+    pub fn get<C: Integer>(&self, point: Point<C>) -> Option<&T> {
+        self.offset(point).map(|offset| &self.data[offset])
+    }
+
+    pub fn get_mut<C: Integer>(&mut self, point: Point<C>) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.data[offset])
+    }
+
+    /**
+     * Returns the in-bounds cells directly above, below, left and right of the given point
+     */
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        point
+            .neighbours4()
+            .filter_map(move |n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /**
+     * Returns the in-bounds cells surrounding the given point, including diagonals
+     */
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        point
+            .neighbours8()
+            .filter_map(move |n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /**
+     * Returns all points reachable from start through cardinal steps onto passable cells
+     */
+    pub fn flood_fill<F>(&self, start: Point, passable: F) -> HashSet<Point>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.bfs(start, passable).into_keys().collect()
+    }
+
+    /**
+     * Breadth-first search from start, returning the step distance to every reachable point.
+     * The start point is always included, regardless of whether it is passable.
+     */
+    pub fn bfs<F>(&self, start: Point, passable: F) -> HashMap<Point, usize>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut distances = HashMap::new();
+        if !self.contains(start) {
+            return distances;
+        }
+
+        let mut queue = VecDeque::from([(start, 0)]);
+        distances.insert(start, 0);
+
+        while let Some((current, distance)) = queue.pop_front() {
+            for (next, cell) in self.neighbours4(current) {
+                if !distances.contains_key(&next) && passable(cell) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        distances
     }
 }
 
@@ -65,24 +332,23 @@ impl<T: Copy + PartialEq + Debug> Grid<T> {
         }
     }
 
-    pub fn col(&self, col_idx: usize) -> Vec<&T> {
+    pub fn col(&self, col_idx: usize) -> Option<Vec<&T>> {
         if col_idx >= self.width {
-            panic!(
-                "Attempted to get column with index {} while grid width is {} (indidices are 0-indexed)",
-                col_idx, self.width
-            );
+            None
+        } else {
+            Some(
+                self.data
+                    .iter()
+                    .skip(col_idx)
+                    .step_by(self.width)
+                    .collect::<Vec<_>>(),
+            )
         }
-
-        self.data
-            .iter()
-            .skip(col_idx)
-            .step_by(self.width)
-            .collect::<Vec<_>>()
     }
 
     pub fn columns(&self) -> Vec<Vec<&T>> {
         (0..self.width)
-            .map(|index| self.col(index))
+            .filter_map(|index| self.col(index))
             .collect::<Vec<_>>()
     }
 
@@ -147,3 +413,190 @@ impl<T: Copy + PartialEq + Debug> Grid<T> {
             .collect::<Vec<_>>()
     }
 }
+
+impl<T, C: Integer> Index<Point<C>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<C>) -> &Self::Output {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "Attempted to index {:?} in a grid of size {}x{}",
+                point, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T, C: Integer> IndexMut<Point<C>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<C>) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point).unwrap_or_else(|| {
+            panic!(
+                "Attempted to index {:?} in a grid of size {}x{}",
+                point, width, height
+            )
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "Attempted to index ({}, {}) in a grid of size {}x{}",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &self.data[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "Attempted to index ({}, {}) in a grid of size {}x{}",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &mut self.data[y * self.width + x]
+    }
+}
+
+impl<T> IntoIterator for Grid<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+/**
+ * Collects an iterator of rows into a grid
+ */
+impl<T, R> FromIterator<R> for Grid<T>
+where
+    R: IntoIterator<Item = T>,
+{
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        Grid::from_rows(
+            iter.into_iter()
+                .map(|row| row.into_iter().collect())
+                .collect(),
+        )
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // not chunks, which panics for a zero width, so such a grid prints its empty rows
+        for index in 0..self.height {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in &self.data[index * self.width..(index + 1) * self.width] {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        error::ParseError,
+        grid::{Grid, ParseGridError},
+        point::Point,
+    };
+
+    #[test]
+    fn neighbours_test() {
+        let grid = Grid::parse("abc\ndef\nghi");
+
+        let corner = grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(
+            vec![(Point::new(0, 1), &'d'), (Point::new(1, 0), &'b')],
+            corner
+        );
+
+        assert_eq!(4, grid.neighbours4(Point::new(1, 1)).count());
+        assert_eq!(8, grid.neighbours8(Point::new(1, 1)).count());
+        assert_eq!(3, grid.neighbours8(Point::new(2, 2)).count());
+    }
+
+    #[test]
+    fn display_round_trip_test() {
+        let input = "#..\n.#.\n..#";
+        let grid: Grid<char> = input.parse().unwrap();
+
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert_eq!(9, grid.bounds().area());
+        assert_eq!(None, grid.col(3));
+        assert_eq!(input, grid.to_string());
+        assert_eq!(grid, grid!["#..", ".#.", "..#"]);
+
+        assert_eq!("\n", Grid::new(0, 2, '.').to_string());
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(Err(ParseGridError::Empty), "".parse::<Grid<char>>());
+        assert_eq!(
+            Err(ParseGridError::UnevenRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            }),
+            "abc\nde".parse::<Grid<char>>()
+        );
+        assert_eq!(
+            ParseError::new(2, 3, "de", "a row of 3 cells"),
+            "abc\nde"
+                .parse::<Grid<char>>()
+                .unwrap_err()
+                .locate("abc\nde")
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 2, "#x", "one of the tiles '.#'")),
+            Grid::parse_tiles("..\n#x", ".#")
+        );
+        assert_eq!(Ok(grid!["..", "#."]), Grid::parse_tiles("..\n#.\n", ".#"));
+    }
+
+    #[test]
+    fn index_and_map_test() {
+        let mut grid = grid![[1, 2, 3], [4, 5, 6]];
+        grid[Point::new(2, 1)] = 9;
+        grid[(0, 0)] = 0;
+
+        assert_eq!(9, grid[(2, 1)]);
+        assert_eq!(0, grid[Point::new(0, 0)]);
+        assert_eq!(grid![[0, 4, 6], [8, 10, 18]], grid.map(|&n| n * 2));
+        assert_eq!(
+            Some((Point::new(1, 1), &5)),
+            grid.iter_points().find(|&(_, &n)| n == 5)
+        );
+        assert_eq!(
+            grid,
+            vec![vec![0, 2, 3], vec![4, 5, 9]].into_iter().collect()
+        );
+        assert_eq!(23, grid.into_iter().sum::<i32>());
+    }
+}
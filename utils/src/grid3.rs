@@ -0,0 +1,283 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ops::{Index, IndexMut},
+};
+
+use crate::{grid::Grid, num::Integer, point::Point3};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Grid3<T> {
+    width: usize,
+    height: usize,
+    depth: usize,
+    data: Vec<T>,
+}
+
+impl<T: Clone> Grid3<T> {
+    pub fn new(width: usize, height: usize, depth: usize, element: T) -> Self {
+        Grid3 {
+            width,
+            height,
+            depth,
+            data: vec![element; width * height * depth],
+        }
+    }
+}
+
+impl<T> Grid3<T> {
+    /**
+     * Wraps data ordered by x, then y, then z in a grid.
+     * Panics if the data does not fill the grid exactly.
+     */
+    pub fn from_vec(width: usize, height: usize, depth: usize, data: Vec<T>) -> Self {
+        assert_eq!(
+            width * height * depth,
+            data.len(),
+            "Attempted to create a {}x{}x{} grid from {} elements",
+            width,
+            height,
+            depth,
+            data.len()
+        );
+        Grid3 {
+            width,
+            height,
+            depth,
+            data,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    fn offset(&self, (x, y, z): (usize, usize, usize)) -> usize {
+        (z * self.height + y) * self.width + x
+    }
+
+    fn checked_offset<C: Integer>(&self, point: Point3<C>) -> Option<usize> {
+        let (x, y, z) = point.to_index()?;
+        if x < self.width && y < self.height && z < self.depth {
+            Some(self.offset((x, y, z)))
+        } else {
+            None
+        }
+    }
+
+    pub fn contains<C: Integer>(&self, point: Point3<C>) -> bool {
+        self.checked_offset(point).is_some()
+    }
+
+    pub fn get<C: Integer>(&self, point: Point3<C>) -> Option<&T> {
+        self.checked_offset(point).map(|offset| &self.data[offset])
+    }
+
+    pub fn get_mut<C: Integer>(&mut self, point: Point3<C>) -> Option<&mut T> {
+        self.checked_offset(point)
+            .map(|offset| &mut self.data[offset])
+    }
+
+    /**
+     * Returns the in-bounds cells sharing a face with the given point
+     */
+    pub fn neighbours6(&self, point: Point3) -> impl Iterator<Item = (Point3, &T)> + '_ {
+        point
+            .neighbours6()
+            .filter_map(move |n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /**
+     * Returns the in-bounds cells sharing a face, edge or corner with the given point
+     */
+    pub fn neighbours26(&self, point: Point3) -> impl Iterator<Item = (Point3, &T)> + '_ {
+        point
+            .neighbours26()
+            .filter_map(move |n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /**
+     * Returns a 2D view of the plane at the given index along an axis.
+     * Slicing along Z yields (x, y), along Y yields (x, z) and along X yields (y, z).
+     */
+    pub fn slice(&self, axis: Axis, index: usize) -> Option<Grid<&T>> {
+        let (width, height, limit) = match axis {
+            Axis::X => (self.height, self.depth, self.width),
+            Axis::Y => (self.width, self.depth, self.height),
+            Axis::Z => (self.width, self.height, self.depth),
+        };
+
+        if index >= limit {
+            return None;
+        }
+
+        let data = (0..height)
+            .flat_map(|v| (0..width).map(move |u| (u, v)))
+            .map(|(u, v)| match axis {
+                Axis::X => &self[(index, u, v)],
+                Axis::Y => &self[(u, index, v)],
+                Axis::Z => &self[(u, v, index)],
+            })
+            .collect::<Vec<_>>();
+
+        Some(Grid::from_vec(width, height, data))
+    }
+
+    /**
+     * Returns all points reachable from start through face-adjacent steps onto passable cells
+     */
+    pub fn flood_fill<F>(&self, start: Point3, passable: F) -> HashSet<Point3>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.bfs(start, passable).into_keys().collect()
+    }
+
+    /**
+     * Breadth-first search from start, returning the step distance to every reachable point.
+     * The start point is always included, regardless of whether it is passable.
+     */
+    pub fn bfs<F>(&self, start: Point3, passable: F) -> HashMap<Point3, usize>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut distances = HashMap::new();
+        if !self.contains(start) {
+            return distances;
+        }
+
+        let mut queue = VecDeque::from([(start, 0)]);
+        distances.insert(start, 0);
+
+        while let Some((current, distance)) = queue.pop_front() {
+            for (next, cell) in self.neighbours6(current) {
+                if !distances.contains_key(&next) && passable(cell) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        distances
+    }
+}
+
+impl<T> Index<(usize, usize, usize)> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize, usize)) -> &Self::Output {
+        let (x, y, z) = index;
+        assert!(
+            x < self.width && y < self.height && z < self.depth,
+            "Attempted to index ({}, {}, {}) in a grid of size {}x{}x{}",
+            x,
+            y,
+            z,
+            self.width,
+            self.height,
+            self.depth
+        );
+        &self.data[self.offset(index)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize, usize)> for Grid3<T> {
+    fn index_mut(&mut self, index: (usize, usize, usize)) -> &mut Self::Output {
+        let (x, y, z) = index;
+        assert!(
+            x < self.width && y < self.height && z < self.depth,
+            "Attempted to index ({}, {}, {}) in a grid of size {}x{}x{}",
+            x,
+            y,
+            z,
+            self.width,
+            self.height,
+            self.depth
+        );
+        let offset = self.offset(index);
+        &mut self.data[offset]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        grid3::{Axis, Grid3},
+        point::Point3,
+    };
+
+    #[test]
+    fn index_test() {
+        let mut grid = Grid3::new(2, 3, 4, 0);
+        grid[(1, 2, 3)] = 7;
+
+        assert_eq!(7, grid[(1, 2, 3)]);
+        assert_eq!(Some(&7), grid.get(Point3::new(1, 2, 3)));
+        assert_eq!(None, grid.get(Point3::new(2, 2, 3)));
+        assert_eq!(None, grid.get(Point3::new(-1, 0, 0)));
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid = Grid3::new(3, 3, 3, '.');
+
+        assert_eq!(6, grid.neighbours6(Point3::new(1, 1, 1)).count());
+        assert_eq!(3, grid.neighbours6(Point3::new(0, 0, 0)).count());
+        assert_eq!(26, grid.neighbours26(Point3::new(1, 1, 1)).count());
+        assert_eq!(7, grid.neighbours26(Point3::new(0, 0, 0)).count());
+    }
+
+    #[test]
+    fn slice_test() {
+        let mut grid = Grid3::new(2, 3, 4, 0);
+        grid[(1, 2, 3)] = 1;
+
+        let z = grid.slice(Axis::Z, 3).unwrap();
+        assert_eq!((2, 3), (z.width(), z.height()));
+        assert_eq!(&1, z[(1, 2)]);
+
+        let y = grid.slice(Axis::Y, 2).unwrap();
+        assert_eq!((2, 4), (y.width(), y.height()));
+        assert_eq!(&1, y[(1, 3)]);
+
+        let x = grid.slice(Axis::X, 1).unwrap();
+        assert_eq!((3, 4), (x.width(), x.height()));
+        assert_eq!(&1, x[(2, 3)]);
+
+        assert!(grid.slice(Axis::Z, 4).is_none());
+    }
+
+    #[test]
+    fn flood_fill_test() {
+        let mut grid = Grid3::new(3, 3, 3, '.');
+        // wall off the z = 1 plane so the fill cannot reach z = 2
+        for x in 0..3 {
+            for y in 0..3 {
+                grid[(x, y, 1)] = '#';
+            }
+        }
+
+        let filled = grid.flood_fill(Point3::new(0, 0, 0), |&c| c == '.');
+        assert_eq!(9, filled.len());
+
+        let distances = grid.bfs(Point3::new(0, 0, 0), |&c| c == '.');
+        assert_eq!(Some(&4), distances.get(&Point3::new(2, 2, 0)));
+    }
+}
@@ -0,0 +1,505 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::point::Point;
+
+/**
+ * Axial coordinates on a hexagonal grid, see https://www.redblobgames.com/grids/hexagons/.
+ * The implicit third cube coordinate is s = -q - r, so that q + r + s = 0.
+ *
+ * The same coordinates serve both flat-topped and pointy-topped maps, only the naming of the six
+ * neighbours differs, see FlatDirection and PointyDirection.
+ */
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+/**
+ * Cube coordinates, an alternative view of Hex where the three axes are symmetric
+ */
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct Cube {
+    pub q: i64,
+    pub r: i64,
+    pub s: i64,
+}
+
+/**
+ * The six unit steps in axial coordinates, in order around a hex starting from +q
+ */
+const UNITS: [Hex; 6] = [
+    Hex::new(1, 0),
+    Hex::new(1, -1),
+    Hex::new(0, -1),
+    Hex::new(-1, 0),
+    Hex::new(-1, 1),
+    Hex::new(0, 1),
+];
+
+/**
+ * How hex rows or columns are shifted when stored in a rectangular Grid.
+ * The R layouts are for pointy-topped maps (rows shifted), the Q layouts for flat-topped maps
+ * (columns shifted). Odd and Even name the rows or columns that are pushed half a cell along.
+ */
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Offset {
+    OddR,
+    EvenR,
+    OddQ,
+    EvenQ,
+}
+
+impl Hex {
+    pub const fn new(q: i64, r: i64) -> Self {
+        Hex { q, r }
+    }
+
+    pub const fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    /**
+     * The number of steps between two hexes
+     */
+    pub fn distance(self, other: Hex) -> i64 {
+        let delta = self - other;
+        (delta.q.abs() + delta.r.abs() + delta.s().abs()) / 2
+    }
+
+    pub fn neighbours(self) -> impl Iterator<Item = Hex> {
+        UNITS.into_iter().map(move |unit| self + unit)
+    }
+
+    /**
+     * All hexes at exactly the given distance, in order around the ring.
+     * A radius of zero yields only the hex itself.
+     */
+    pub fn ring(self, radius: u32) -> impl Iterator<Item = Hex> {
+        let radius = radius as i64;
+        let start = self + UNITS[4] * radius;
+        let steps = if radius == 0 { 1 } else { 6 * radius };
+
+        (0..steps).scan(start, move |current, step| {
+            let hex = *current;
+            if radius > 0 {
+                *current += UNITS[(step / radius) as usize];
+            }
+            Some(hex)
+        })
+    }
+
+    /**
+     * All hexes within the given distance, ring by ring from the centre outwards
+     */
+    pub fn spiral(self, radius: u32) -> impl Iterator<Item = Hex> {
+        (0..=radius).flat_map(move |r| self.ring(r))
+    }
+
+    /**
+     * The (column, row) position of this hex in a rectangular grid using the given layout
+     */
+    pub fn to_offset(self, layout: Offset) -> Point {
+        let Hex { q, r } = self;
+        match layout {
+            Offset::OddR => Point::new(q + (r - (r & 1)) / 2, r),
+            Offset::EvenR => Point::new(q + (r + (r & 1)) / 2, r),
+            Offset::OddQ => Point::new(q, r + (q - (q & 1)) / 2),
+            Offset::EvenQ => Point::new(q, r + (q + (q & 1)) / 2),
+        }
+    }
+
+    /**
+     * The hex stored at (column, row) in a rectangular grid using the given layout
+     */
+    pub fn from_offset(point: Point, layout: Offset) -> Self {
+        let Point { x, y } = point;
+        match layout {
+            Offset::OddR => Hex::new(x - (y - (y & 1)) / 2, y),
+            Offset::EvenR => Hex::new(x - (y + (y & 1)) / 2, y),
+            Offset::OddQ => Hex::new(x, y - (x - (x & 1)) / 2),
+            Offset::EvenQ => Hex::new(x, y - (x + (x & 1)) / 2),
+        }
+    }
+}
+
+impl Cube {
+    /**
+     * Panics if the coordinates do not sum to zero
+     */
+    pub fn new(q: i64, r: i64, s: i64) -> Self {
+        assert_eq!(
+            0,
+            q + r + s,
+            "Cube coordinates ({}, {}, {}) must sum to zero",
+            q,
+            r,
+            s
+        );
+        Cube { q, r, s }
+    }
+
+    pub fn distance(self, other: Cube) -> i64 {
+        Hex::from(self).distance(other.into())
+    }
+}
+
+impl From<Hex> for Cube {
+    fn from(hex: Hex) -> Self {
+        Cube {
+            q: hex.q,
+            r: hex.r,
+            s: hex.s(),
+        }
+    }
+}
+
+impl From<Cube> for Hex {
+    fn from(cube: Cube) -> Self {
+        Hex::new(cube.q, cube.r)
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Self;
+
+    fn mul(self, scalar: i64) -> Self::Output {
+        Hex::new(self.q * scalar, self.r * scalar)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Hex {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseHexDirectionError {
+    found: String,
+    expected: &'static str,
+}
+
+impl Display for ParseHexDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a hex direction, expected one of {}",
+            self.found, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseHexDirectionError {}
+
+/**
+ * The neighbours of a flat-topped hex, which has edges to the north and south
+ */
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum FlatDirection {
+    NORTH,
+    NORTHEAST,
+    SOUTHEAST,
+    SOUTH,
+    SOUTHWEST,
+    NORTHWEST,
+}
+
+impl FlatDirection {
+    /**
+     * All directions clockwise from north
+     */
+    pub const fn all() -> [FlatDirection; 6] {
+        [
+            FlatDirection::NORTH,
+            FlatDirection::NORTHEAST,
+            FlatDirection::SOUTHEAST,
+            FlatDirection::SOUTH,
+            FlatDirection::SOUTHWEST,
+            FlatDirection::NORTHWEST,
+        ]
+    }
+
+    pub const fn unit(self) -> Hex {
+        match self {
+            FlatDirection::NORTH => Hex::new(0, -1),
+            FlatDirection::NORTHEAST => Hex::new(1, -1),
+            FlatDirection::SOUTHEAST => Hex::new(1, 0),
+            FlatDirection::SOUTH => Hex::new(0, 1),
+            FlatDirection::SOUTHWEST => Hex::new(-1, 1),
+            FlatDirection::NORTHWEST => Hex::new(-1, 0),
+        }
+    }
+
+    pub const fn opposite(self) -> Self {
+        match self {
+            FlatDirection::NORTH => FlatDirection::SOUTH,
+            FlatDirection::NORTHEAST => FlatDirection::SOUTHWEST,
+            FlatDirection::SOUTHEAST => FlatDirection::NORTHWEST,
+            FlatDirection::SOUTH => FlatDirection::NORTH,
+            FlatDirection::SOUTHWEST => FlatDirection::NORTHEAST,
+            FlatDirection::NORTHWEST => FlatDirection::SOUTHEAST,
+        }
+    }
+}
+
+impl From<FlatDirection> for Hex {
+    fn from(direction: FlatDirection) -> Self {
+        direction.unit()
+    }
+}
+
+impl FromStr for FlatDirection {
+    type Err = ParseHexDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "n" => Ok(FlatDirection::NORTH),
+            "ne" => Ok(FlatDirection::NORTHEAST),
+            "se" => Ok(FlatDirection::SOUTHEAST),
+            "s" => Ok(FlatDirection::SOUTH),
+            "sw" => Ok(FlatDirection::SOUTHWEST),
+            "nw" => Ok(FlatDirection::NORTHWEST),
+            _ => Err(ParseHexDirectionError {
+                found: s.to_owned(),
+                expected: "n/ne/se/s/sw/nw",
+            }),
+        }
+    }
+}
+
+/**
+ * The neighbours of a pointy-topped hex, which has edges to the east and west
+ */
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PointyDirection {
+    EAST,
+    SOUTHEAST,
+    SOUTHWEST,
+    WEST,
+    NORTHWEST,
+    NORTHEAST,
+}
+
+impl PointyDirection {
+    /**
+     * All directions clockwise from east
+     */
+    pub const fn all() -> [PointyDirection; 6] {
+        [
+            PointyDirection::EAST,
+            PointyDirection::SOUTHEAST,
+            PointyDirection::SOUTHWEST,
+            PointyDirection::WEST,
+            PointyDirection::NORTHWEST,
+            PointyDirection::NORTHEAST,
+        ]
+    }
+
+    pub const fn unit(self) -> Hex {
+        match self {
+            PointyDirection::EAST => Hex::new(1, 0),
+            PointyDirection::SOUTHEAST => Hex::new(0, 1),
+            PointyDirection::SOUTHWEST => Hex::new(-1, 1),
+            PointyDirection::WEST => Hex::new(-1, 0),
+            PointyDirection::NORTHWEST => Hex::new(0, -1),
+            PointyDirection::NORTHEAST => Hex::new(1, -1),
+        }
+    }
+
+    pub const fn opposite(self) -> Self {
+        match self {
+            PointyDirection::EAST => PointyDirection::WEST,
+            PointyDirection::SOUTHEAST => PointyDirection::NORTHWEST,
+            PointyDirection::SOUTHWEST => PointyDirection::NORTHEAST,
+            PointyDirection::WEST => PointyDirection::EAST,
+            PointyDirection::NORTHWEST => PointyDirection::SOUTHEAST,
+            PointyDirection::NORTHEAST => PointyDirection::SOUTHWEST,
+        }
+    }
+
+    /**
+     * Splits an undelimited path such as "esenee" into its steps
+     */
+    pub fn parse_path(path: &str) -> Result<Vec<PointyDirection>, ParseHexDirectionError> {
+        let mut chars = path.trim().chars();
+        let mut steps = Vec::new();
+
+        while let Some(c) = chars.next() {
+            let mut step = c.to_string();
+            if matches!(c.to_ascii_lowercase(), 'n' | 's') {
+                step.extend(chars.next());
+            }
+            steps.push(step.parse()?);
+        }
+        Ok(steps)
+    }
+}
+
+impl From<PointyDirection> for Hex {
+    fn from(direction: PointyDirection) -> Self {
+        direction.unit()
+    }
+}
+
+impl FromStr for PointyDirection {
+    type Err = ParseHexDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "e" => Ok(PointyDirection::EAST),
+            "se" => Ok(PointyDirection::SOUTHEAST),
+            "sw" => Ok(PointyDirection::SOUTHWEST),
+            "w" => Ok(PointyDirection::WEST),
+            "nw" => Ok(PointyDirection::NORTHWEST),
+            "ne" => Ok(PointyDirection::NORTHEAST),
+            _ => Err(ParseHexDirectionError {
+                found: s.to_owned(),
+                expected: "e/se/sw/w/nw/ne",
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::{
+        grid::Grid,
+        hex::{Cube, FlatDirection, Hex, Offset, PointyDirection},
+        point::Point,
+    };
+
+    #[test]
+    fn flat_path_test() {
+        // examples from 2017 day 11
+        let walk = |path: &str| {
+            path.split(',')
+                .map(|step| step.parse::<FlatDirection>().unwrap().unit())
+                .fold(Hex::default(), |hex, step| hex + step)
+        };
+
+        assert_eq!(3, walk("ne,ne,ne").distance(Hex::default()));
+        assert_eq!(0, walk("ne,ne,sw,sw").distance(Hex::default()));
+        assert_eq!(2, walk("ne,ne,s,s").distance(Hex::default()));
+        assert_eq!(3, walk("se,sw,se,sw,sw").distance(Hex::default()));
+        assert!("x".parse::<FlatDirection>().is_err());
+    }
+
+    #[test]
+    fn pointy_path_test() {
+        // examples from 2020 day 24
+        let path = PointyDirection::parse_path("nwwswee").unwrap();
+        assert_eq!(
+            vec![
+                PointyDirection::NORTHWEST,
+                PointyDirection::WEST,
+                PointyDirection::SOUTHWEST,
+                PointyDirection::EAST,
+                PointyDirection::EAST
+            ],
+            path
+        );
+        assert_eq!(
+            Hex::default(),
+            path.into_iter()
+                .map(Hex::from)
+                .fold(Hex::default(), |a, b| a + b)
+        );
+
+        let end = PointyDirection::parse_path("esew")
+            .unwrap()
+            .into_iter()
+            .fold(Hex::default(), |hex, step| hex + step.unit());
+        assert_eq!(PointyDirection::SOUTHEAST.unit(), end);
+        assert!(PointyDirection::parse_path("en").is_err());
+    }
+
+    #[test]
+    fn direction_test() {
+        for direction in FlatDirection::all() {
+            assert_eq!(-direction.unit(), direction.opposite().unit());
+        }
+        for direction in PointyDirection::all() {
+            assert_eq!(-direction.unit(), direction.opposite().unit());
+        }
+        assert_eq!(6, Hex::new(2, 3).neighbours().collect::<HashSet<_>>().len());
+    }
+
+    #[test]
+    fn cube_test() {
+        let cube = Cube::from(Hex::new(3, -5));
+        assert_eq!(Cube::new(3, -5, 2), cube);
+        assert_eq!(Hex::new(3, -5), Hex::from(cube));
+        assert_eq!(5, cube.distance(Cube::default()));
+    }
+
+    #[test]
+    fn ring_and_spiral_test() {
+        let centre = Hex::new(1, -2);
+
+        assert_eq!(vec![centre], centre.ring(0).collect::<Vec<_>>());
+
+        let ring = centre.ring(2).collect::<Vec<_>>();
+        assert_eq!(12, ring.len());
+        assert_eq!(12, ring.iter().collect::<HashSet<_>>().len());
+        assert!(ring.iter().all(|&hex| hex.distance(centre) == 2));
+
+        let spiral = centre.spiral(3).collect::<HashSet<_>>();
+        assert_eq!(37, spiral.len());
+        assert!(spiral.iter().all(|&hex| hex.distance(centre) <= 3));
+    }
+
+    #[test]
+    fn offset_test() {
+        for layout in [Offset::OddR, Offset::EvenR, Offset::OddQ, Offset::EvenQ] {
+            for hex in Hex::default().spiral(4) {
+                assert_eq!(hex, Hex::from_offset(hex.to_offset(layout), layout));
+            }
+        }
+
+        // in odd-r the odd rows are shifted right, so (0, 1) neighbours both (0, 0) and (1, 0)
+        let grid = Grid::from_vec(2, 2, vec!['a', 'b', 'c', 'd']);
+        let hex = Hex::from_offset(Point::new(0, 1), Offset::OddR);
+        let neighbours = hex
+            .neighbours()
+            .filter_map(|n| grid.get(n.to_offset(Offset::OddR)))
+            .collect::<HashSet<_>>();
+        assert_eq!(HashSet::from([&'a', &'b', &'d']), neighbours);
+    }
+}
@@ -1,5 +1,12 @@
+pub mod beam;
+pub mod error;
+pub mod geometry;
 pub mod grid;
+pub mod grid3;
+pub mod hex;
 pub mod num;
+pub mod parse;
 pub mod point;
+pub mod scan;
 pub mod string;
 pub mod vector;
@@ -1,23 +1,737 @@
-pub fn lcm(first: usize, second: usize) -> usize {
-    first * second / gcd(first, second)
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, RangeInclusive, Rem, Sub, SubAssign},
+};
+
+pub mod linalg;
+pub mod matrix;
+pub mod modular;
+pub mod poly;
+pub mod prime;
+pub mod rational;
+
+pub use matrix::{matrix_pow, Matrix};
+pub use modular::Modular;
+pub use prime::{divisors, factorize, is_prime, primes, primes_in, totient};
+pub use rational::Rational;
+
+/**
+ * Common operations over the primitive integer types
+ */
+pub trait Integer:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + TryFrom<usize>
+    + TryInto<usize>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    /**
+     * The absolute value, or None when it does not fit in the type, e.g. for i64::MIN
+     */
+    fn checked_abs(self) -> Option<Self>;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /**
+     * The remainder, treating MIN % -1 as 0 rather than overflowing
+     */
+    fn wrapping_rem(self, other: Self) -> Self;
+
+    /**
+     * The remainder in [0, |other|), also for negative values
+     */
+    fn rem_euclid(self, other: Self) -> Self;
+
+    /**
+     * The absolute difference between two values, without leaving the type
+     */
+    fn distance(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+/**
+ * Integers that can be negative, needed wherever coefficients or differences may drop below zero
+ */
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    (@common $t:ty) => {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+        const MIN: Self = <$t>::MIN;
+        const MAX: Self = <$t>::MAX;
+
+        fn checked_add(self, other: Self) -> Option<Self> {
+            <$t>::checked_add(self, other)
+        }
+
+        fn checked_sub(self, other: Self) -> Option<Self> {
+            <$t>::checked_sub(self, other)
+        }
+
+        fn checked_mul(self, other: Self) -> Option<Self> {
+            <$t>::checked_mul(self, other)
+        }
+
+        fn wrapping_rem(self, other: Self) -> Self {
+            <$t>::wrapping_rem(self, other)
+        }
+
+        fn rem_euclid(self, other: Self) -> Self {
+            <$t>::rem_euclid(self, other)
+        }
+    };
+    (signed: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                impl_integer!(@common $t);
+
+                fn checked_abs(self) -> Option<Self> {
+                    <$t>::checked_abs(self)
+                }
+            }
+
+            impl Signed for $t {}
+        )*
+    };
+    (unsigned: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                impl_integer!(@common $t);
+
+                fn checked_abs(self) -> Option<Self> {
+                    Some(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(signed: i8, i16, i32, i64, i128, isize);
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize);
+
+/**
+ * The greatest common divisor, which is never negative. gcd(0, n) is |n| and gcd(0, 0) is 0.
+ * Returns None when the result does not fit, which only happens for gcd(MIN, 0) and gcd(MIN, MIN)
+ * of a signed type.
+ */
+pub fn checked_gcd<T: Integer>(first: T, second: T) -> Option<T> {
+    let (mut a, mut b) = (first, second);
+    while b != T::ZERO {
+        (a, b) = (b, a.wrapping_rem(b));
+    }
+    a.checked_abs()
+}
+
+/**
+ * Panics when the result does not fit in the type, see checked_gcd
+ */
+pub fn gcd<T: Integer>(first: T, second: T) -> T {
+    checked_gcd(first, second).unwrap_or_else(|| panic!("gcd({}, {}) overflows", first, second))
+}
+
+/**
+ * The least common multiple, which is never negative. lcm(0, n) is 0.
+ * Returns None when the result does not fit in the type.
+ */
+pub fn checked_lcm<T: Integer>(first: T, second: T) -> Option<T> {
+    if first == T::ZERO || second == T::ZERO {
+        return Some(T::ZERO);
+    }
+    // divide before multiplying so only a result that truly overflows fails
+    (first / checked_gcd(first, second)?)
+        .checked_mul(second)?
+        .checked_abs()
+}
+
+/**
+ * Panics when the result does not fit in the type, see checked_lcm
+ */
+pub fn lcm<T: Integer>(first: T, second: T) -> T {
+    checked_lcm(first, second).unwrap_or_else(|| panic!("lcm({}, {}) overflows", first, second))
+}
+
+/**
+ * The greatest common divisor of all values, 0 for no values
+ */
+pub fn checked_gcd_all<T, I>(values: I) -> Option<T>
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    values
+        .into_iter()
+        .try_fold(T::ZERO, |acc, value| checked_gcd(acc, value))
+}
+
+pub fn gcd_all<T, I>(values: I) -> T
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/**
+ * The least common multiple of all values, 1 for no values
+ */
+pub fn checked_lcm_all<T, I>(values: I) -> Option<T>
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    values
+        .into_iter()
+        .try_fold(T::ONE, |acc, value| checked_lcm(acc, value))
+}
+
+pub fn lcm_all<T, I>(values: I) -> T
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+/**
+ * Solves a * x + b * y = gcd(a, b) using the extended Euclidean algorithm, returning
+ * (gcd, x, y) with a non-negative gcd.
+ * Returns None when the gcd does not fit in the type, as for checked_gcd.
+ */
+pub fn checked_extended_gcd<T: Signed>(a: T, b: T) -> Option<(T, T, T)> {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let remainder = old_r.wrapping_rem(r);
+        if remainder == T::ZERO {
+            // r is the gcd up to sign. The next coefficients would be a and b divided by it, which
+            // are not needed and overflow for MIN / -1.
+            (old_r, old_x, old_y) = (r, x, y);
+            break;
+        }
+        let quotient = old_r / r;
+        (old_r, r) = (r, remainder);
+        (old_x, x) = (x, old_x.checked_sub(quotient.checked_mul(x)?)?);
+        (old_y, y) = (y, old_y.checked_sub(quotient.checked_mul(y)?)?);
+    }
+
+    if old_r < T::ZERO {
+        Some((
+            T::ZERO.checked_sub(old_r)?,
+            T::ZERO.checked_sub(old_x)?,
+            T::ZERO.checked_sub(old_y)?,
+        ))
+    } else {
+        Some((old_r, old_x, old_y))
+    }
+}
+
+/**
+ * Panics when the gcd does not fit in the type, see checked_extended_gcd
+ */
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    checked_extended_gcd(a, b).unwrap_or_else(|| panic!("extended_gcd({}, {}) overflows", a, b))
+}
+
+/**
+ * The x in [0, modulus) with a * x = 1 (mod modulus), or None when a and the modulus share a
+ * factor or the modulus is not positive
+ */
+pub fn mod_inverse<T: Signed>(a: T, modulus: T) -> Option<T> {
+    if modulus <= T::ZERO {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == T::ONE).then(|| x.rem_euclid(modulus))
+}
+
+/**
+ * (a + b) % modulus for a and b in [0, modulus), without overflowing
+ */
+pub(crate) fn add_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/**
+ * (a * b) mod modulus in [0, modulus), falling back to double-and-add when the product would
+ * overflow. Panics if the modulus is not positive.
+ */
+pub fn mul_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    assert!(modulus > T::ZERO, "modulus {} must be positive", modulus);
+    let (mut a, mut b) = (a.rem_euclid(modulus), b.rem_euclid(modulus));
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+
+    let two = T::ONE + T::ONE;
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b = b / two;
+    }
+    result
+}
+
+/**
+ * base ^ exponent mod modulus in [0, modulus) by repeated squaring, without overflowing.
+ * Panics if the modulus is not positive.
+ */
+pub fn mod_pow<T: Integer>(base: T, exponent: u64, modulus: T) -> T {
+    assert!(modulus > T::ZERO, "modulus {} must be positive", modulus);
+    let mut base = base.rem_euclid(modulus);
+    let mut exponent = exponent;
+    let mut result = T::ONE % modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/**
+ * Solves the system x = residue (mod modulus) for every (residue, modulus) pair using the Chinese
+ * Remainder Theorem. The moduli need not be coprime.
+ *
+ * Returns (x, m) where m is the lcm of the moduli and x in [0, m) is the smallest solution, so
+ * every solution is x + k * m. Returns None when the congruences contradict each other, when a
+ * modulus is not positive, or when m overflows.
+ */
+pub fn crt<T, I>(congruences: I) -> Option<(T, T)>
+where
+    T: Signed,
+    I: IntoIterator<Item = (T, T)>,
+{
+    congruences
+        .into_iter()
+        .try_fold((T::ZERO, T::ONE), |(x, m), (residue, modulus)| {
+            if modulus <= T::ZERO {
+                return None;
+            }
+            let residue = residue.rem_euclid(modulus);
+
+            // x + m * k = residue (mod modulus) -> m * k = residue - x (mod modulus)
+            let (g, p, _) = extended_gcd(m, modulus);
+            let difference = residue - x.rem_euclid(modulus);
+            if difference % g != T::ZERO {
+                return None;
+            }
+
+            let step = modulus / g;
+            let k = mul_mod(difference / g, p, step);
+            let combined = (m / g).checked_mul(modulus)?;
+            let x = add_mod(x, mul_mod(m, k, combined), combined);
+            Some((x, combined))
+        })
 }
 
-pub fn gcd(first: usize, second: usize) -> usize {
-    let mut max = first;
-    let mut min = second;
+/**
+ * The largest x with x * x <= n
+ */
+pub fn isqrt(n: u64) -> u64 {
+    isqrt_u128(n as u128) as u64
+}
 
-    if min > max {
-        let val = max;
-        max = min;
-        min = val;
+/**
+ * The largest x with x * x <= n, using Newton's method from an initial guess above the root
+ */
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
     }
 
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1u128 << bits.div_ceil(2);
     loop {
-        let res = max % min;
-        if res == 0 {
-            return min;
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
         }
-        max = min;
-        min = res;
+        x = next;
+    }
+}
+
+/**
+ * A 256 bit two's complement integer as (high, low), wide enough for products of 128 bit values
+ */
+type Wide = (i128, u128);
+
+fn widen(value: i128) -> Wide {
+    (if value < 0 { -1 } else { 0 }, value as u128)
+}
+
+/**
+ * The full product of two unsigned values as (high, low), multiplying their 64 bit halves
+ */
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    let mask = u64::MAX as u128;
+    let (a_high, a_low) = (a >> 64, a & mask);
+    let (b_high, b_low) = (b >> 64, b & mask);
+
+    let low = a_low * b_low;
+    let middle = a_high * b_low + (low >> 64);
+    let other_middle = a_low * b_high + (middle & mask);
+    let high = a_high * b_high + (middle >> 64) + (other_middle >> 64);
+    (high, (other_middle << 64) | (low & mask))
+}
+
+/**
+ * The magnitude with the given sign, for magnitudes below 2^255
+ */
+fn signed_wide(negative: bool, (high, low): (u128, u128)) -> Wide {
+    if negative {
+        let (low, carry) = (!low).overflowing_add(1);
+        ((!high as i128).wrapping_add(carry as i128), low)
+    } else {
+        (high as i128, low)
+    }
+}
+
+fn add_wide((a_high, a_low): Wide, (b_high, b_low): Wide) -> Wide {
+    let (low, carry) = a_low.overflowing_add(b_low);
+    (a_high + b_high + carry as i128, low)
+}
+
+/**
+ * Whether -a * x^2 + b * x + c > 0 for a positive a, checked exactly as x * (a * x - b) < c
+ */
+fn is_solution(a: u128, b: i128, c: i128, x: i128) -> bool {
+    if x == 0 {
+        return c > 0;
+    }
+
+    let ax = signed_wide(x < 0, mul_wide(a, x.unsigned_abs()));
+    let (high, low) = add_wide(ax, signed_wide(b > 0, (0, b.unsigned_abs())));
+    if high != 0 && (high != -1 || low == 0) {
+        // |a * x - b| >= 2^128, so the product outweighs c and only its sign matters
+        return (x < 0) != (high < 0);
+    }
+
+    let magnitude = if high < 0 { low.wrapping_neg() } else { low };
+    let product = signed_wide((x < 0) != (high < 0), mul_wide(x.unsigned_abs(), magnitude));
+    product < widen(c)
+}
+
+/**
+ * The first x in [low, high] for which the predicate holds, given that it holds for high and keeps
+ * holding once it does
+ */
+fn partition_point(mut low: i128, mut high: i128, holds: impl Fn(i128) -> bool) -> i128 {
+    while low < high {
+        let middle = low + (high.abs_diff(low) / 2) as i128;
+        if holds(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    low
+}
+
+/**
+ * All integers x with a * x^2 + b * x + c > 0. Every candidate is evaluated exactly in 256 bit
+ * arithmetic, and the first and last solutions are found by bisection on either side of the vertex,
+ * so any i128 coefficients take at most a few hundred steps and never overflow.
+ *
+ * Returns None when there are infinitely many solutions, which is the case for an upwards opening
+ * parabola or a non-constant line. No solutions give an empty range.
+ */
+pub fn integer_solutions(a: i128, b: i128, c: i128) -> Option<RangeInclusive<i128>> {
+    let empty = RangeInclusive::new(1, 0);
+    if a == 0 {
+        return match (b, c > 0) {
+            (0, true) => None,
+            (0, false) => Some(empty),
+            _ => None,
+        };
+    }
+    if a > 0 {
+        return None;
+    }
+
+    // the vertex lies in [vertex, vertex + 1) where vertex = floor(b / 2|a|) = floor(floor(b / 2) / |a|)
+    let a = a.unsigned_abs();
+    let half = b.div_euclid(2);
+    let vertex = i128::try_from(a).map_or(if half < 0 { -1 } else { 0 }, |a| half.div_euclid(a));
+
+    // the parabola rises up to the vertex and falls after it, so the best integer is next to it
+    let inside = |x: i128| is_solution(a, b, c, x);
+    let Some(peak) = [vertex, vertex + 1].into_iter().find(|&x| inside(x)) else {
+        return Some(empty);
+    };
+    let low = partition_point(i128::MIN, peak, inside);
+    let high = partition_point(peak, i128::MAX, |x| x == i128::MAX || !inside(x + 1));
+
+    Some(low..=high)
+}
+
+/**
+ * The number of integers x with a * x^2 + b * x + c > 0, or None when there are infinitely many.
+ * See integer_solutions.
+ */
+pub fn count_integer_solutions(a: i128, b: i128, c: i128) -> Option<u128> {
+    integer_solutions(a, b, c).map(|range| {
+        if range.is_empty() {
+            0
+        } else {
+            range.end().abs_diff(*range.start()) + 1
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num::{
+        checked_extended_gcd, checked_gcd, checked_gcd_all, checked_lcm, checked_lcm_all,
+        count_integer_solutions, crt, extended_gcd, gcd, gcd_all, integer_solutions, isqrt,
+        isqrt_u128, lcm, lcm_all, mod_inverse, mod_pow, mul_mod,
+    };
+
+    #[test]
+    fn gcd_test() {
+        assert_eq!(6, gcd(12, 18));
+        assert_eq!(6, gcd(18usize, 12));
+        assert_eq!(1, gcd(17u8, 5));
+        assert_eq!(7, gcd(0, 7));
+        assert_eq!(7, gcd(7, 0));
+        assert_eq!(0, gcd(0, 0));
+    }
+
+    #[test]
+    fn gcd_negative_test() {
+        assert_eq!(4, gcd(-8, 12));
+        assert_eq!(4, gcd(8, -12));
+        assert_eq!(4, gcd(-8i64, -12));
+        assert_eq!(5, gcd(-5, 0));
+        assert_eq!(1, gcd(i64::MIN, -1));
+        assert_eq!(Some(1 << 62), checked_gcd(i64::MIN, 1 << 62));
+        assert_eq!(None, checked_gcd(i64::MIN, 0));
+        assert_eq!(None, checked_gcd(i32::MIN, i32::MIN));
+    }
+
+    #[test]
+    fn lcm_test() {
+        assert_eq!(36, lcm(12, 18));
+        assert_eq!(0, lcm(0, 5));
+        assert_eq!(0, lcm(0u32, 0));
+        assert_eq!(12, lcm(-4, 6));
+        assert_eq!(12, lcm(-4, -6));
+    }
+
+    #[test]
+    fn lcm_overflow_test() {
+        // the product overflows but the lcm itself fits
+        let big = u64::MAX / 3;
+        assert_eq!(big, lcm(big, big));
+        assert_eq!(Some(u64::MAX / 3 * 2), checked_lcm(big, 2));
+        assert_eq!(Some(i64::MAX), checked_lcm(i64::MAX, 1));
+        assert_eq!(None, checked_lcm(u64::MAX, u64::MAX - 1));
+        assert_eq!(None, checked_lcm(i64::MIN, 1));
+        assert_eq!(None, checked_lcm(200u8, 3));
+    }
+
+    #[test]
+    fn all_test() {
+        assert_eq!(6, gcd_all([12, -18, 30]));
+        assert_eq!(0, gcd_all(Vec::<i64>::new()));
+        assert_eq!(2520, lcm_all(1..=10u64));
+        assert_eq!(1, lcm_all(Vec::<u64>::new()));
+        assert_eq!(0, lcm_all([3, 0, 5]));
+        assert_eq!(Some(5), checked_gcd_all([-10i8, 15]));
+        assert_eq!(None, checked_lcm_all(1..=30u32));
+        assert_eq!(Some(2329089562800), checked_lcm_all(1..=30u64));
+    }
+
+    #[test]
+    fn extended_gcd_test() {
+        assert_eq!((2, -9, 47), extended_gcd(240, 46));
+        for (a, b) in [(240i64, 46), (-240, 46), (17, -5), (0, 7), (7, 0), (0, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(gcd(a, b), g);
+            assert_eq!(g, a * x + b * y);
+        }
+    }
+
+    #[test]
+    fn extended_gcd_overflow_test() {
+        assert_eq!(Some((1, 0, 1)), checked_extended_gcd(i64::MIN, 1));
+        assert_eq!(Some((1, 0, -1)), checked_extended_gcd(i64::MIN, -1));
+        assert_eq!(Some((1, -1, -1)), checked_extended_gcd(i8::MAX, i8::MIN));
+        assert_eq!(None, checked_extended_gcd(i64::MIN, 0));
+        assert_eq!(None, checked_extended_gcd(i32::MIN, i32::MIN));
+    }
+
+    #[test]
+    fn mod_inverse_test() {
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(Some(7), mod_inverse(-3, 11));
+        assert_eq!(None, mod_inverse(6, 9));
+        assert_eq!(None, mod_inverse(3, 0));
+        assert_eq!(Some(0), mod_inverse(5, 1));
+    }
+
+    #[test]
+    fn mul_mod_test() {
+        assert_eq!(6, mul_mod(7, 8, 10));
+        assert_eq!(4, mul_mod(-7, 8, 10));
+        let big = i64::MAX - 24;
+        assert_eq!(
+            ((big as i128 - 1) * (big as i128 - 2) % big as i128) as i64,
+            mul_mod(big - 1, big - 2, big)
+        );
+    }
+
+    #[test]
+    fn mod_pow_test() {
+        assert_eq!(24, mod_pow(2, 10, 1000));
+        assert_eq!(1, mod_pow(0, 0, 7));
+        assert_eq!(0, mod_pow(5, 0, 1));
+        assert_eq!(2, mod_pow(-3, 3, 29));
+
+        // Fermat's little theorem with the Mersenne prime 2^61 - 1, whose squares overflow i64
+        let prime = (1i64 << 61) - 1;
+        assert_eq!(1, mod_pow(123_456_789, prime as u64 - 1, prime));
+        assert_eq!(
+            mod_inverse(123_456_789, prime),
+            Some(mod_pow(123_456_789, prime as u64 - 2, prime))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus 0 must be positive")]
+    fn mod_pow_zero_modulus_test() {
+        mod_pow(2, 3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus -5 must be positive")]
+    fn mod_pow_negative_modulus_test() {
+        mod_pow(2, 0, -5);
+    }
+
+    #[test]
+    fn crt_test() {
+        assert_eq!(Some((23, 105)), crt([(2, 3), (3, 5), (2, 7)]));
+        // non-coprime moduli that agree on their shared factor
+        assert_eq!(Some((10, 12)), crt([(4, 6), (2, 4)]));
+        // and ones that contradict each other
+        assert_eq!(None, crt([(1, 6), (2, 4)]));
+        assert_eq!(Some((0, 1)), crt(Vec::<(i64, i64)>::new()));
+        assert_eq!(Some((3, 5)), crt([(-2, 5)]));
+        assert_eq!(None, crt([(0, 0)]));
+        assert_eq!(None, crt([(0, i64::MAX), (0, i64::MAX - 1)]));
+
+        // large coprime moduli whose product only just fits
+        let (p, q) = (2_147_483_647i64, 4_294_967_291);
+        let (x, m) = crt([(5, p), (7, q)]).unwrap();
+        assert_eq!(p * q, m);
+        assert_eq!((5, 7), (x % p, x % q));
+    }
+
+    #[test]
+    fn isqrt_test() {
+        assert_eq!(0, isqrt(0));
+        assert_eq!(1, isqrt(3));
+        assert_eq!(2, isqrt(4));
+        assert_eq!(u32::MAX as u64, isqrt(u64::MAX));
+        assert_eq!(3_037_000_499, isqrt(i64::MAX as u64));
+        assert_eq!(u64::MAX as u128, isqrt_u128(u128::MAX));
+        for root in [1u128, 7, 1 << 40, (1 << 63) + 12345, u64::MAX as u128] {
+            assert_eq!(root, isqrt_u128(root * root));
+            assert_eq!(root - 1, isqrt_u128(root * root - 1));
+        }
+    }
+
+    #[test]
+    fn integer_solutions_test() {
+        // -x^2 + 7x - 9 > 0, the first race of 2023 day 6
+        assert_eq!(Some(2..=5), integer_solutions(-1, 7, -9));
+        // -x^2 + 30x - 200 > 0 touches zero at both 10 and 20, which are excluded
+        assert_eq!(Some(11..=19), integer_solutions(-1, 30, -200));
+        assert_eq!(Some(9), count_integer_solutions(-1, 30, -200));
+        // -x^2 + 4 > 0 and -4x^2 - 3x + 1 > 0, whose roots are -1 and 1/4
+        assert_eq!(Some(-1..=1), integer_solutions(-1, 0, 4));
+        assert_eq!(Some(0..=0), integer_solutions(-4, -3, 1));
+        // a maximum of exactly zero or below
+        assert_eq!(Some(0), count_integer_solutions(-1, 2, -1));
+        assert_eq!(Some(0), count_integer_solutions(-1, 0, -5));
+        // unbounded or degenerate
+        assert_eq!(None, integer_solutions(1, 0, -5));
+        assert_eq!(None, integer_solutions(0, 2, 1));
+        assert_eq!(None, integer_solutions(0, 0, 1));
+        assert_eq!(Some(0), count_integer_solutions(0, 0, 0));
+    }
+
+    #[test]
+    fn integer_solutions_large_test() {
+        // a race lasting 2^62 ms with a record just below the best possible distance
+        let time = 1i128 << 62;
+        let best = (time / 2) * (time / 2);
+        assert_eq!(Some(1), count_integer_solutions(-1, time, -(best - 1)));
+        assert_eq!(Some(0), count_integer_solutions(-1, time, -best));
+        assert_eq!(Some(time as u128 - 1), count_integer_solutions(-1, time, 0));
+    }
+
+    #[test]
+    fn integer_solutions_extreme_test() {
+        assert_eq!(Some(0..=0), integer_solutions(i128::MIN, 0, i128::MAX));
+        assert_eq!(Some(0), count_integer_solutions(i128::MIN, i128::MIN, 0));
+        assert_eq!(
+            Some(0..=i128::MAX),
+            integer_solutions(-1, i128::MAX, i128::MAX)
+        );
+        assert_eq!(
+            Some(i128::MIN + 2..=-2),
+            integer_solutions(-1, i128::MIN, i128::MIN)
+        );
+        assert_eq!(
+            Some(i128::MAX as u128 - 1),
+            count_integer_solutions(-1, i128::MAX, 0)
+        );
+        assert_eq!(
+            Some(i128::MAX as u128 - 3),
+            count_integer_solutions(-1, i128::MAX, -i128::MAX)
+        );
+        // -3x^2 + 2^100x - 2^126 > 0, whose discriminant needs more than 128 bits
+        assert_eq!(
+            Some(67_108_865..=422_550_200_076_076_467_165_500_626_261),
+            integer_solutions(-3, 1 << 100, -(1 << 126))
+        );
     }
 }
@@ -0,0 +1,266 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::num::{Modular, Rational, Signed};
+
+/**
+ * Values that can be added, subtracted, multiplied and divided exactly, like fractions or
+ * integers modulo a prime. Floating point numbers are deliberately left out, since elimination
+ * needs exact comparisons against zero.
+ */
+pub trait Field:
+    Copy
+    + PartialEq
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+impl<T: Signed> Field for Rational<T> {
+    fn zero() -> Self {
+        Rational::zero()
+    }
+
+    fn one() -> Self {
+        Rational::one()
+    }
+}
+
+impl<const P: u64> Field for Modular<P> {
+    fn zero() -> Self {
+        Modular::new(0)
+    }
+
+    fn one() -> Self {
+        Modular::new(1)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Solution<F> {
+    Unique(Vec<F>),
+    /**
+     * Infinitely many solutions: particular + t_1 * basis[0] + t_2 * basis[1] + ... for any
+     * choice of the parameters t_i, one per free variable
+     */
+    Underdetermined {
+        particular: Vec<F>,
+        basis: Vec<Vec<F>>,
+    },
+    Inconsistent,
+}
+
+/**
+ * Solves A x = b by Gauss-Jordan elimination, with one row of A per equation.
+ * Panics when the rows of A differ in length or there are not as many rows as values in b, and
+ * over a Modular with a composite modulus when a pivot has no inverse.
+ */
+pub fn solve<F: Field>(a: &[Vec<F>], b: &[F]) -> Solution<F> {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "Attempted to solve {} equations with {} right hand sides",
+        a.len(),
+        b.len()
+    );
+    let columns = a.first().map_or(0, Vec::len);
+    assert!(
+        a.iter().all(|row| row.len() == columns),
+        "Attempted to solve a system whose equations have different numbers of unknowns"
+    );
+
+    // the augmented matrix [A | b], reduced in place to reduced row echelon form
+    let mut rows = a
+        .iter()
+        .zip(b)
+        .map(|(row, &value)| {
+            let mut row = row.clone();
+            row.push(value);
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let mut pivots = vec![];
+    for column in 0..columns {
+        let row = pivots.len();
+        let Some(pivot) = (row..rows.len()).find(|&r| !rows[r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(row, pivot);
+
+        let scale = rows[row][column];
+        for value in rows[row].iter_mut() {
+            *value = *value / scale;
+        }
+        let pivot_row = rows[row].clone();
+        for (other, values) in rows.iter_mut().enumerate() {
+            let factor = values[column];
+            if other != row && !factor.is_zero() {
+                for (value, &subtrahend) in values.iter_mut().zip(&pivot_row).skip(column) {
+                    *value = *value - factor * subtrahend;
+                }
+            }
+        }
+        pivots.push(column);
+    }
+
+    // a leftover row 0 = c with c non-zero can never hold
+    if rows[pivots.len()..]
+        .iter()
+        .any(|row| !row[columns].is_zero())
+    {
+        return Solution::Inconsistent;
+    }
+
+    let mut particular = vec![F::zero(); columns];
+    for (row, &column) in pivots.iter().enumerate() {
+        particular[column] = rows[row][columns];
+    }
+    if pivots.len() == columns {
+        return Solution::Unique(particular);
+    }
+
+    let basis = (0..columns)
+        .filter(|column| !pivots.contains(column))
+        .map(|free| {
+            let mut direction = vec![F::zero(); columns];
+            direction[free] = F::one();
+            for (row, &column) in pivots.iter().enumerate() {
+                direction[column] = -rows[row][free];
+            }
+            direction
+        })
+        .collect();
+
+    Solution::Underdetermined { particular, basis }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num::{
+        linalg::{solve, Field, Solution},
+        Modular, Rational,
+    };
+
+    fn matrix(rows: &[&[i128]]) -> Vec<Vec<Rational>> {
+        rows.iter()
+            .map(|row| row.iter().map(|&value| Rational::from(value)).collect())
+            .collect()
+    }
+
+    fn vector(values: &[i128]) -> Vec<Rational> {
+        values.iter().map(|&value| Rational::from(value)).collect()
+    }
+
+    fn apply<F: Field>(a: &[Vec<F>], x: &[F]) -> Vec<F> {
+        a.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(x)
+                    .fold(F::zero(), |sum, (&coefficient, &value)| {
+                        sum + coefficient * value
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unique_test() {
+        let a = matrix(&[&[2, 1, -1], &[-3, -1, 2], &[-2, 1, 2]]);
+        let b = vector(&[8, -11, -3]);
+        assert_eq!(Solution::Unique(vector(&[2, 3, -1])), solve(&a, &b));
+
+        // fractional solution: 2x + 4y = 1, x - y = 0
+        let a = matrix(&[&[2, 4], &[1, -1]]);
+        let b = vector(&[1, 0]);
+        assert_eq!(
+            Solution::Unique(vec![Rational::new(1, 6), Rational::new(1, 6)]),
+            solve(&a, &b)
+        );
+
+        // more equations than unknowns, but all consistent
+        let a = matrix(&[&[1, 0], &[0, 1], &[1, 1]]);
+        let b = vector(&[4, 5, 9]);
+        assert_eq!(Solution::Unique(vector(&[4, 5])), solve(&a, &b));
+    }
+
+    #[test]
+    fn inconsistent_test() {
+        let a = matrix(&[&[1, 1], &[2, 2]]);
+        assert_eq!(Solution::Inconsistent, solve(&a, &vector(&[1, 3])));
+
+        let a = matrix(&[&[1, 0], &[0, 1], &[1, 1]]);
+        assert_eq!(Solution::Inconsistent, solve(&a, &vector(&[4, 5, 8])));
+    }
+
+    #[test]
+    fn underdetermined_test() {
+        // x + y + z = 6 and x - y = 0 leave one free variable
+        let a = matrix(&[&[1, 1, 1], &[1, -1, 0]]);
+        let b = vector(&[6, 0]);
+
+        let Solution::Underdetermined { particular, basis } = solve(&a, &b) else {
+            panic!("expected infinitely many solutions");
+        };
+        assert_eq!(1, basis.len());
+        assert_eq!(b, apply(&a, &particular));
+        for t in [-3, 1, 7] {
+            let x = particular
+                .iter()
+                .zip(&basis[0])
+                .map(|(&p, &d)| p + Rational::from(t) * d)
+                .collect::<Vec<_>>();
+            assert_eq!(b, apply(&a, &x));
+        }
+
+        // an empty system has only the empty solution
+        let empty: Vec<Vec<Rational>> = vec![];
+        assert_eq!(Solution::Unique(vec![]), solve(&empty, &[]));
+    }
+
+    #[test]
+    fn modular_test() {
+        type Mod7 = Modular<7>;
+        let a = vec![
+            vec![Mod7::new(3), Mod7::new(1)],
+            vec![Mod7::new(1), Mod7::new(2)],
+        ];
+        let b = vec![Mod7::new(4), Mod7::new(2)];
+
+        let Solution::Unique(x) = solve(&a, &b) else {
+            panic!("expected a unique solution");
+        };
+        assert_eq!(b, apply(&a, &x));
+
+        // 2x + 4y = 1 and x + 2y = 4 are the same line modulo 7
+        let a = vec![
+            vec![Mod7::new(2), Mod7::new(4)],
+            vec![Mod7::new(1), Mod7::new(2)],
+        ];
+        let b = vec![Mod7::new(1), Mod7::new(4)];
+        assert!(matches!(
+            solve(&a, &b),
+            Solution::Underdetermined { ref basis, .. } if basis.len() == 1
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn composite_modulus_test() {
+        // 2x = 2 has both 1 and 4 as solutions modulo 6, and 2 cannot be divided by
+        type Mod6 = Modular<6>;
+        let _ = solve(&[vec![Mod6::new(2)]], &[Mod6::new(2)]);
+    }
+}
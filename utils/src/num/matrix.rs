@@ -0,0 +1,180 @@
+use std::ops::{Index, IndexMut, Mul};
+
+use crate::num::{add_mod, mul_mod, Integer};
+
+/**
+ * A fixed-size square matrix, mostly useful to repeat a linear transformation many times.
+ *
+ * A linear recurrence such as f(n) = f(n - 1) + f(n - 2) becomes a matrix acting on the vector of
+ * its last N values, so step n is reached with matrix_pow in O(N^3 log n) instead of n steps.
+ * Affine maps like the card shuffles of 2019 day 22 (x -> a * x + b) fit by appending a constant
+ * 1 to the vector.
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Matrix<const N: usize, T = i64> {
+    data: [[T; N]; N],
+}
+
+impl<const N: usize, T: Integer> Matrix<N, T> {
+    pub const fn new(rows: [[T; N]; N]) -> Self {
+        Matrix { data: rows }
+    }
+
+    pub fn zero() -> Self {
+        Matrix::new([[T::ZERO; N]; N])
+    }
+
+    pub fn identity() -> Self {
+        let mut identity = Self::zero();
+        for i in 0..N {
+            identity.data[i][i] = T::ONE;
+        }
+        identity
+    }
+
+    pub fn rows(&self) -> &[[T; N]; N] {
+        &self.data
+    }
+
+    fn reduce(&self, modulus: T) -> Self {
+        Matrix::new(
+            self.data
+                .map(|row| row.map(|value| value.rem_euclid(modulus))),
+        )
+    }
+
+    /**
+     * The matrix product with every entry reduced into [0, modulus), without overflowing
+     */
+    pub fn mul_mod(&self, other: &Self, modulus: T) -> Self {
+        let mut product = Self::zero();
+        for i in 0..N {
+            for j in 0..N {
+                product.data[i][j] = (0..N).fold(T::ZERO, |sum, k| {
+                    add_mod(
+                        sum,
+                        mul_mod(self.data[i][k], other.data[k][j], modulus),
+                        modulus,
+                    )
+                });
+            }
+        }
+        product
+    }
+
+    /**
+     * Multiplies a column vector with the matrix, reducing every entry into [0, modulus)
+     */
+    pub fn apply_mod(&self, vector: [T; N], modulus: T) -> [T; N] {
+        let mut result = [T::ZERO; N];
+        for (i, row) in self.data.iter().enumerate() {
+            result[i] = row.iter().zip(vector).fold(T::ZERO, |sum, (&a, b)| {
+                add_mod(sum, mul_mod(a, b, modulus), modulus)
+            });
+        }
+        result
+    }
+}
+
+/**
+ * matrix ^ exponent with every entry reduced into [0, modulus), by repeated squaring.
+ * Panics if the modulus is not positive.
+ */
+pub fn matrix_pow<const N: usize, T: Integer>(
+    matrix: &Matrix<N, T>,
+    exponent: u64,
+    modulus: T,
+) -> Matrix<N, T> {
+    let mut base = matrix.reduce(modulus);
+    let mut exponent = exponent;
+    let mut result = Matrix::identity().reduce(modulus);
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result.mul_mod(&base, modulus);
+        }
+        base = base.mul_mod(&base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+impl<const N: usize, T: Integer> Mul for Matrix<N, T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let mut product = Self::zero();
+        for i in 0..N {
+            for j in 0..N {
+                product.data[i][j] =
+                    (0..N).fold(T::ZERO, |sum, k| sum + self.data[i][k] * other.data[k][j]);
+            }
+        }
+        product
+    }
+}
+
+impl<const N: usize, T> Index<(usize, usize)> for Matrix<N, T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.data[row][col]
+    }
+}
+
+impl<const N: usize, T> IndexMut<(usize, usize)> for Matrix<N, T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[row][col]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num::{matrix_pow, Matrix};
+
+    #[test]
+    fn fibonacci_test() {
+        let fibonacci = Matrix::new([[1, 1], [1, 0]]);
+
+        assert_eq!(55, matrix_pow(&fibonacci, 10, 1_000)[(0, 1)]);
+        assert_eq!(
+            2_880_067_194_370_816_120,
+            matrix_pow(&fibonacci, 90, i64::MAX)[(0, 1)]
+        );
+        assert_eq!(Matrix::identity(), matrix_pow(&fibonacci, 0, 7));
+        assert_eq!(fibonacci * fibonacci, matrix_pow(&fibonacci, 2, 100));
+    }
+
+    #[test]
+    fn power_laws_test() {
+        let modulus = 1_000_000_007i64;
+        let matrix = Matrix::new([[2, 7, 1], [0, 3, 5], [8, 1, 1]]);
+
+        let a = matrix_pow(&matrix, 123_456_789_012, modulus);
+        let b = matrix_pow(&matrix, 987_654_321, modulus);
+        assert_eq!(
+            matrix_pow(&matrix, 123_456_789_012 + 987_654_321, modulus),
+            a.mul_mod(&b, modulus)
+        );
+    }
+
+    #[test]
+    fn affine_shuffle_test() {
+        // 2019 day 22: deal with increment 7, then deal into new stack twice, on a deck of 10
+        let deck = 10;
+        let increment = Matrix::new([[7, 0], [0, 1]]);
+        let new_stack = Matrix::new([[-1, -1], [0, 1]]);
+        let shuffle = new_stack
+            .mul_mod(&new_stack, deck)
+            .mul_mod(&increment, deck);
+
+        let positions = (0..deck)
+            .map(|card| shuffle.apply_mod([card, 1], deck)[0])
+            .collect::<Vec<_>>();
+        assert_eq!(vec![0, 7, 4, 1, 8, 5, 2, 9, 6, 3], positions);
+
+        // shuffling twice is the same as applying the squared matrix
+        let twice = matrix_pow(&shuffle, 2, deck);
+        assert_eq!(9, twice.apply_mod([1, 1], deck)[0]);
+    }
+}
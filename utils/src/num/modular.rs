@@ -0,0 +1,163 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::num::{mod_inverse, mul_mod, Integer};
+
+/**
+ * An element of the integers modulo P, kept in [0, P).
+ * Only values sharing no factor with P have an inverse to divide by, so the values form a field
+ * when P is prime. Moduli below 2 are rejected when compiling:
+ *
+ * ```compile_fail
+ * let value = utils::num::Modular::<1>::new(3);
+ * ```
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Modular<const P: u64> {
+    value: u64,
+}
+
+impl<const P: u64> Modular<P> {
+    const VALID_MODULUS: () = assert!(P >= 2, "the modulus of Modular has to be at least 2");
+
+    pub fn new(value: i128) -> Self {
+        let () = Self::VALID_MODULUS;
+        Modular {
+            value: value.rem_euclid(P as i128) as u64,
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /**
+     * The multiplicative inverse, or None when the value shares a factor with P, which for a prime
+     * P only happens for zero
+     */
+    pub fn inverse(&self) -> Option<Self> {
+        mod_inverse(self.value as i128, P as i128).map(|value| Modular {
+            value: value as u64,
+        })
+    }
+}
+
+impl<const P: u64> Add for Modular<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Modular::new(self.value as i128 + other.value as i128)
+    }
+}
+
+impl<const P: u64> Sub for Modular<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Modular::new(self.value as i128 - other.value as i128)
+    }
+}
+
+impl<const P: u64> Mul for Modular<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Modular {
+            value: mul_mod(self.value as u128, other.value as u128, P as u128) as u64,
+        }
+    }
+}
+
+impl<const P: u64> Div for Modular<P> {
+    type Output = Self;
+
+    /**
+     * Panics when the divisor has no inverse, such as zero
+     */
+    fn div(self, other: Self) -> Self::Output {
+        let inverse = other.inverse().unwrap_or_else(|| {
+            panic!(
+                "Attempted to divide {} by {}, which has no inverse modulo {}",
+                self, other, P
+            )
+        });
+        self.mul(inverse)
+    }
+}
+
+impl<const P: u64> Neg for Modular<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Modular::new(-(self.value as i128))
+    }
+}
+
+impl<const P: u64> Display for Modular<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<const P: u64, T: Integer> From<T> for Modular<P>
+where
+    i128: TryFrom<T>,
+{
+    /**
+     * Panics for u128 values beyond i128::MAX
+     */
+    fn from(value: T) -> Self {
+        Modular::new(
+            i128::try_from(value).unwrap_or_else(|_| panic!("{} does not fit in an i128", value)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num::Modular;
+
+    type Mod7 = Modular<7>;
+
+    #[test]
+    fn arithmetic_test() {
+        let (a, b) = (Mod7::new(5), Mod7::new(-3));
+
+        assert_eq!(4, b.value());
+        assert_eq!(Mod7::new(2), a + b);
+        assert_eq!(Mod7::new(1), a - b);
+        assert_eq!(Mod7::new(6), a * b);
+        assert_eq!(Mod7::new(3), a / b);
+        assert_eq!(Mod7::new(2), -a);
+        assert_eq!(Some(Mod7::new(3)), a.inverse());
+        assert_eq!(None, Mod7::new(14).inverse());
+        assert_eq!(Mod7::from(12u8), Mod7::new(5));
+    }
+
+    #[test]
+    fn large_modulus_test() {
+        const PRIME: u64 = (1 << 61) - 1;
+        let value = Modular::<PRIME>::new(-123_456_789_123);
+
+        assert_eq!(Modular::new(1), value * value.inverse().unwrap());
+    }
+
+    #[test]
+    fn composite_modulus_test() {
+        type Mod6 = Modular<6>;
+
+        assert_eq!(Some(Mod6::new(5)), Mod6::new(5).inverse());
+        assert_eq!(None, Mod6::new(2).inverse());
+        assert_eq!(None, Mod6::new(3).inverse());
+        assert_eq!(Some(Modular::<4>::new(3)), Modular::<4>::new(3).inverse());
+        assert_eq!(Mod6::new(2), Mod6::new(4) / Mod6::new(5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divide_by_non_invertible_test() {
+        let _ = Modular::<4>::new(1) / Modular::<4>::new(2);
+    }
+}
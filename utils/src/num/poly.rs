@@ -0,0 +1,272 @@
+use std::fmt::{self, Display};
+
+use crate::num::Rational;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PolyError {
+    /**
+     * The differences never became all zero, so the values do not pin down a polynomial
+     */
+    NotPolynomial,
+    DuplicateX(i128),
+    Overflow,
+}
+
+impl Display for PolyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolyError::NotPolynomial => {
+                write!(f, "the sequence is not generated by a polynomial")
+            }
+            PolyError::DuplicateX(x) => write!(f, "x = {} appears more than once", x),
+            PolyError::Overflow => write!(f, "the result does not fit in an i128"),
+        }
+    }
+}
+
+impl std::error::Error for PolyError {}
+
+/**
+ * The finite-difference table of a sequence: the sequence itself, then the differences between
+ * neighbouring values, then their differences and so on, ending with the first row of zeros.
+ * When the values run out before reaching zeros, the table ends with a single-value row.
+ */
+pub fn difference_table(sequence: &[i128]) -> Result<Vec<Vec<i128>>, PolyError> {
+    let mut table = vec![sequence.to_vec()];
+
+    while let Some(row) = table.last() {
+        if row.len() <= 1 || row.iter().all(|&value| value == 0) {
+            break;
+        }
+        let next = row
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(pair[0]))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(PolyError::Overflow)?;
+        table.push(next);
+    }
+    Ok(table)
+}
+
+/**
+ * The degree of the polynomial generating the sequence, where the zero sequence has degree 0.
+ * Confirming degree d takes at least d + 2 values, since the next row of differences must be zero.
+ */
+pub fn degree(sequence: &[i128]) -> Result<usize, PolyError> {
+    Ok(polynomial_table(sequence)?.len().saturating_sub(2))
+}
+
+/**
+ * The difference table, provided it ends in a row of zeros
+ */
+fn polynomial_table(sequence: &[i128]) -> Result<Vec<Vec<i128>>, PolyError> {
+    let table = difference_table(sequence)?;
+    match table.last() {
+        Some(row) if !row.is_empty() && row.iter().all(|&value| value == 0) => Ok(table),
+        _ => Err(PolyError::NotPolynomial),
+    }
+}
+
+/**
+ * The value at x = k of the polynomial through (0, sequence[0]), (1, sequence[1]), ...
+ * k may lie anywhere, so k = sequence.len() predicts the next value and k = -1 the previous one.
+ *
+ * Uses Newton's forward difference formula p(k) = sum of binomial(k, j) * (j-th difference at 0),
+ * which stays in integers since binomial(k, j) is an integer for every integer k.
+ */
+pub fn extrapolate(sequence: &[i128], k: i128) -> Result<i128, PolyError> {
+    let table = polynomial_table(sequence)?;
+    let degree = table.len().saturating_sub(2);
+
+    let mut binomial: i128 = 1;
+    let mut value: i128 = 0;
+    for (j, row) in table.iter().take(degree + 1).enumerate() {
+        if j > 0 {
+            // binomial(k, j) = binomial(k, j - 1) * (k - j + 1) / j, where the division is exact
+            let j = j as i128;
+            binomial = binomial.checked_mul(k - j + 1).ok_or(PolyError::Overflow)? / j;
+        }
+        value = binomial
+            .checked_mul(row[0])
+            .and_then(|term| value.checked_add(term))
+            .ok_or(PolyError::Overflow)?;
+    }
+    Ok(value)
+}
+
+/**
+ * A polynomial with exact rational coefficients, lowest power first
+ */
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Polynomial {
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<Rational>) -> Self {
+        let mut coefficients = coefficients;
+        while coefficients.last().is_some_and(Rational::is_zero) {
+            coefficients.pop();
+        }
+        Polynomial { coefficients }
+    }
+
+    /**
+     * The unique polynomial of lowest degree through all points, using Newton's divided
+     * differences (https://en.wikipedia.org/wiki/Newton_polynomial)
+     */
+    pub fn interpolate(points: &[(i128, i128)]) -> Result<Self, PolyError> {
+        for (index, &(x, _)) in points.iter().enumerate() {
+            if points[..index].iter().any(|&(other, _)| other == x) {
+                return Err(PolyError::DuplicateX(x));
+            }
+        }
+
+        let xs = points
+            .iter()
+            .map(|&(x, _)| Rational::from(x))
+            .collect::<Vec<_>>();
+        let mut divided = points
+            .iter()
+            .map(|&(_, y)| Rational::from(y))
+            .collect::<Vec<_>>();
+
+        // after round j, divided[i] holds f[x_(i - j), ..., x_i] for every i >= j
+        for j in 1..points.len() {
+            for i in (j..points.len()).rev() {
+                divided[i] = (divided[i] - divided[i - 1]) / (xs[i] - xs[i - j]);
+            }
+        }
+
+        // expand c_0 + c_1 (x - x_0) + c_2 (x - x_0)(x - x_1) + ... into plain coefficients
+        let mut coefficients = vec![Rational::zero(); points.len()];
+        let mut basis = vec![Rational::one()];
+        for (coefficient, &x) in divided.iter().zip(&xs) {
+            for (target, &b) in coefficients.iter_mut().zip(&basis) {
+                *target += *coefficient * b;
+            }
+            basis = multiply_linear(&basis, x);
+        }
+        Ok(Polynomial::new(coefficients))
+    }
+
+    /**
+     * The polynomial generating the sequence, with sequence[i] as its value at x = i.
+     * Fails when the sequence is not polynomial, see degree.
+     */
+    pub fn fit(sequence: &[i128]) -> Result<Self, PolyError> {
+        let degree = degree(sequence)?;
+        let points = sequence
+            .iter()
+            .take(degree + 1)
+            .enumerate()
+            .map(|(x, &y)| (x as i128, y))
+            .collect::<Vec<_>>();
+        Polynomial::interpolate(&points)
+    }
+
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    /**
+     * The highest power with a non-zero coefficient, or None for the zero polynomial
+     */
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn evaluate(&self, x: Rational) -> Rational {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Rational::zero(), |acc, &coefficient| acc * x + coefficient)
+    }
+}
+
+/**
+ * Multiplies a polynomial by (x - root)
+ */
+fn multiply_linear(coefficients: &[Rational], root: Rational) -> Vec<Rational> {
+    let mut product = vec![Rational::zero(); coefficients.len() + 1];
+    for (power, &coefficient) in coefficients.iter().enumerate() {
+        product[power + 1] += coefficient;
+        product[power] -= coefficient * root;
+    }
+    product
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num::{
+        poly::{degree, difference_table, extrapolate, PolyError, Polynomial},
+        Rational,
+    };
+
+    #[test]
+    fn difference_table_test() {
+        assert_eq!(
+            vec![vec![1, 3, 6, 10], vec![2, 3, 4], vec![1, 1], vec![0]],
+            difference_table(&[1, 3, 6, 10]).unwrap()
+        );
+        assert_eq!(Ok(2), degree(&[1, 3, 6, 10]));
+        assert_eq!(Ok(0), degree(&[5, 5]));
+        assert_eq!(Ok(0), degree(&[0, 0, 0]));
+        assert_eq!(Err(PolyError::NotPolynomial), degree(&[1, 3, 6]));
+        assert_eq!(Err(PolyError::NotPolynomial), degree(&[]));
+    }
+
+    #[test]
+    fn extrapolate_test() {
+        // the histories from the 2023 day 9 example
+        let histories = [
+            ([0, 3, 6, 9, 12, 15], 18, -3),
+            ([1, 3, 6, 10, 15, 21], 28, 0),
+            ([10, 13, 16, 21, 30, 45], 68, 5),
+        ];
+        for (history, next, previous) in histories {
+            assert_eq!(Ok(next), extrapolate(&history, 6));
+            assert_eq!(Ok(previous), extrapolate(&history, -1));
+        }
+
+        let squares = [0, 1, 4, 9];
+        assert_eq!(Ok(9), extrapolate(&squares, -3));
+        assert_eq!(Ok(10i128.pow(30)), extrapolate(&squares, 10i128.pow(15)));
+        assert_eq!(
+            Err(PolyError::Overflow),
+            extrapolate(&squares, 10i128.pow(20))
+        );
+    }
+
+    #[test]
+    fn not_polynomial_test() {
+        let powers = [1, 2, 4, 8, 16, 32, 64];
+        assert_eq!(Err(PolyError::NotPolynomial), extrapolate(&powers, 7));
+        assert_eq!(Err(PolyError::NotPolynomial), Polynomial::fit(&powers));
+    }
+
+    #[test]
+    fn interpolate_test() {
+        let triangular = Polynomial::fit(&[0, 1, 3, 6, 10]).unwrap();
+        assert_eq!(
+            &[Rational::zero(), Rational::new(1, 2), Rational::new(1, 2)],
+            triangular.coefficients()
+        );
+        assert_eq!(Some(2), triangular.degree());
+        assert_eq!(
+            Rational::from(5050),
+            triangular.evaluate(Rational::from(100))
+        );
+
+        let line = Polynomial::interpolate(&[(1, 1), (3, 2)]).unwrap();
+        assert_eq!(Rational::new(3, 2), line.evaluate(Rational::from(2)));
+
+        let constant = Polynomial::interpolate(&[(-2, 0), (5, 0)]).unwrap();
+        assert_eq!(None, constant.degree());
+
+        assert_eq!(
+            Err(PolyError::DuplicateX(3)),
+            Polynomial::interpolate(&[(3, 1), (4, 2), (3, 5)])
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::ops::Range;
+
+use crate::num::{gcd, isqrt, mod_pow, mul_mod};
+
+/**
+ * The number of values sieved at once, small enough for the segment to stay in cache
+ */
+const SEGMENT_SIZE: u64 = 1 << 15;
+
+/**
+ * All primes up to and including the limit
+ */
+pub fn primes(limit: u64) -> Vec<u64> {
+    primes_in(0..limit.saturating_add(1))
+}
+
+/**
+ * All primes in the range using a segmented sieve of Eratosthenes: the primes up to
+ * sqrt(range.end) are sieved first and then used to cross off one segment of the range at a
+ * time, so memory stays proportional to sqrt(range.end) + SEGMENT_SIZE.
+ */
+pub fn primes_in(range: Range<u64>) -> Vec<u64> {
+    let start = range.start.max(2);
+    if start >= range.end {
+        return vec![];
+    }
+
+    let base = simple_sieve(isqrt(range.end - 1));
+    let mut primes = vec![];
+    let mut low = start;
+
+    while low < range.end {
+        let high = low.saturating_add(SEGMENT_SIZE).min(range.end);
+        let mut composite = vec![false; (high - low) as usize];
+
+        for &prime in &base {
+            // start crossing off at prime^2, everything below has a smaller factor
+            let first = (prime * prime).max(low.div_ceil(prime) * prime);
+            for multiple in (first..high).step_by(prime as usize) {
+                composite[(multiple - low) as usize] = true;
+            }
+        }
+
+        primes.extend(
+            composite
+                .iter()
+                .enumerate()
+                .filter(|(_, &is_composite)| !is_composite)
+                .map(|(offset, _)| low + offset as u64),
+        );
+        low = high;
+    }
+    primes
+}
+
+fn simple_sieve(limit: u64) -> Vec<u64> {
+    let limit = limit as usize;
+    let mut composite = vec![false; limit + 1];
+    let mut primes = vec![];
+
+    for n in 2..=limit {
+        if !composite[n] {
+            primes.push(n as u64);
+            for multiple in (n * n..=limit).step_by(n) {
+                composite[multiple] = true;
+            }
+        }
+    }
+    primes
+}
+
+/**
+ * Deterministic Miller-Rabin test. The first twelve primes as witnesses are enough to decide
+ * every 64 bit number (https://oeis.org/A014233).
+ */
+pub fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    for &p in &WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    // n - 1 = d * 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let n = n as u128;
+
+    WITNESSES.iter().all(|&witness| {
+        let mut x = mod_pow(witness as u128, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/**
+ * The prime factorisation as (prime, exponent) pairs in increasing order of prime.
+ * Small factors are found by trial division and the rest with Pollard's rho algorithm.
+ * 0 and 1 have no prime factors.
+ */
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    if n == 0 {
+        return vec![];
+    }
+
+    let mut factors = vec![];
+    let mut remaining = n;
+    for p in (2..100).filter(|&p| is_prime(p)) {
+        while remaining.is_multiple_of(p) {
+            remaining /= p;
+            factors.push(p);
+        }
+    }
+
+    let mut stack = vec![remaining];
+    while let Some(value) = stack.pop() {
+        if value == 1 {
+            continue;
+        }
+        if is_prime(value) {
+            factors.push(value);
+            continue;
+        }
+        let divisor = pollard_rho(value);
+        stack.push(divisor);
+        stack.push(value / divisor);
+    }
+
+    factors.sort_unstable();
+    let mut powers: Vec<(u64, u32)> = vec![];
+    for factor in factors {
+        match powers.last_mut() {
+            Some((prime, exponent)) if *prime == factor => *exponent += 1,
+            _ => powers.push((factor, 1)),
+        }
+    }
+    powers
+}
+
+/**
+ * A non-trivial divisor of a composite n without small factors
+ */
+fn pollard_rho(n: u64) -> u64 {
+    let n128 = n as u128;
+    let step = |x: u64, c: u64| ((mul_mod(x as u128, x as u128, n128) + c as u128) % n128) as u64;
+
+    // retry with another constant in the rare case the cycle closes without finding a divisor
+    for c in 1.. {
+        let (mut tortoise, mut hare, mut divisor) = (2, 2, 1);
+        while divisor == 1 {
+            tortoise = step(tortoise, c);
+            hare = step(step(hare, c), c);
+            divisor = gcd(tortoise.abs_diff(hare), n);
+        }
+        if divisor != n {
+            return divisor;
+        }
+    }
+    unreachable!()
+}
+
+/**
+ * All positive divisors in increasing order, none for 0
+ */
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return vec![];
+    }
+
+    let mut divisors = vec![1];
+    for (prime, exponent) in factorize(n) {
+        let mut extended = Vec::with_capacity(divisors.len() * (exponent as usize + 1));
+        for &divisor in &divisors {
+            let mut power = 1;
+            for _ in 0..=exponent {
+                extended.push(divisor * power);
+                power *= prime;
+            }
+        }
+        divisors = extended;
+    }
+    divisors.sort_unstable();
+    divisors
+}
+
+/**
+ * Euler's totient: the number of values in 1..=n that share no factor with n
+ */
+pub fn totient(n: u64) -> u64 {
+    factorize(n)
+        .iter()
+        .fold(n, |result, &(prime, _)| result / prime * (prime - 1))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num::prime::{divisors, factorize, is_prime, primes, primes_in, totient};
+
+    #[test]
+    fn sieve_test() {
+        assert_eq!(vec![2, 3, 5, 7, 11, 13, 17, 19], primes(20));
+        assert_eq!(Vec::<u64>::new(), primes(1));
+        assert_eq!(vec![2], primes(2));
+        assert_eq!(78_498, primes(1_000_000).len());
+
+        // a range spanning several segments, far from zero
+        let start = 1_000_000_000;
+        let segmented = primes_in(start..start + 100_000);
+        assert_eq!(Some(&1_000_000_007), segmented.first());
+        assert!(segmented.iter().all(|&p| is_prime(p)));
+        assert_eq!(
+            (start..start + 100_000).filter(|&n| is_prime(n)).count(),
+            segmented.len()
+        );
+    }
+
+    #[test]
+    fn is_prime_test() {
+        assert!(!is_prime(0));
+        assert!(!is_prime(1));
+        assert!(is_prime(2));
+        assert!(is_prime(97));
+        assert!(!is_prime(561)); // Carmichael number
+        assert!(!is_prime(3_215_031_751)); // strong pseudoprime to bases 2, 3, 5 and 7
+        assert!(is_prime(18_446_744_073_709_551_557)); // largest 64 bit prime
+        assert!(!is_prime(u64::MAX));
+        assert!(is_prime((1 << 61) - 1));
+    }
+
+    #[test]
+    fn factorize_test() {
+        assert_eq!(Vec::<(u64, u32)>::new(), factorize(1));
+        assert_eq!(vec![(2, 3), (3, 2), (5, 1)], factorize(360));
+        assert_eq!(vec![(97, 1)], factorize(97));
+        assert_eq!(
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6_700_417, 1)
+            ],
+            factorize(u64::MAX)
+        );
+        // two large primes that trial division alone would never find
+        assert_eq!(
+            vec![(1_000_000_007, 1), (4_294_967_291, 1)],
+            factorize(1_000_000_007 * 4_294_967_291)
+        );
+    }
+
+    #[test]
+    fn divisors_and_totient_test() {
+        assert_eq!(vec![1, 2, 3, 4, 6, 12], divisors(12));
+        assert_eq!(vec![1], divisors(1));
+        assert_eq!(Vec::<u64>::new(), divisors(0));
+        assert_eq!(24, divisors(360).len());
+
+        assert_eq!(1, totient(1));
+        assert_eq!(4, totient(12));
+        assert_eq!(96, totient(97));
+        assert_eq!(
+            (1..=360).filter(|&k| crate::num::gcd(k, 360) == 1).count() as u64,
+            totient(360)
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::num::{gcd, Signed};
+
+/**
+ * An exact fraction numerator / denominator.
+ * It is kept normalised: the denominator is positive and shares no factor with the numerator,
+ * so equal values compare equal.
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational<T = i128> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T: Signed> Rational<T> {
+    /**
+     * Panics if the denominator is zero
+     */
+    pub fn new(numerator: T, denominator: T) -> Self {
+        assert!(
+            denominator != T::ZERO,
+            "Rational denominator cannot be zero"
+        );
+
+        let divisor = gcd(numerator, denominator);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+        if denominator < T::ZERO {
+            Rational {
+                numerator: -numerator,
+                denominator: -denominator,
+            }
+        } else {
+            Rational {
+                numerator,
+                denominator,
+            }
+        }
+    }
+
+    pub fn integer(value: T) -> Self {
+        Rational {
+            numerator: value,
+            denominator: T::ONE,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Rational::integer(T::ZERO)
+    }
+
+    pub fn one() -> Self {
+        Rational::integer(T::ONE)
+    }
+
+    pub fn numerator(&self) -> T {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> T {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == T::ZERO
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == T::ONE
+    }
+
+    /**
+     * The value as an integer, or None if it has a fractional part
+     */
+    pub fn to_integer(&self) -> Option<T> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    /**
+     * The largest integer not above the value
+     */
+    pub fn floor(&self) -> T {
+        let quotient = self.numerator / self.denominator;
+        if self.numerator % self.denominator < T::ZERO {
+            quotient - T::ONE
+        } else {
+            quotient
+        }
+    }
+
+    /**
+     * The smallest integer not below the value
+     */
+    pub fn ceil(&self) -> T {
+        if self.is_integer() {
+            self.numerator
+        } else {
+            self.floor() + T::ONE
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.numerator < T::ZERO {
+            -*self
+        } else {
+            *self
+        }
+    }
+
+    /**
+     * 1 / value, or None for zero
+     */
+    pub fn recip(&self) -> Option<Self> {
+        (!self.is_zero()).then(|| Rational::new(self.denominator, self.numerator))
+    }
+
+    /**
+     * The fractional part value - floor(value), in [0, 1)
+     */
+    fn fraction(&self) -> Self {
+        Rational {
+            numerator: self.numerator.rem_euclid(self.denominator),
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl<T: Signed> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        // scale both sides to the lcm of the denominators to keep the intermediate values small
+        let divisor = gcd(self.denominator, other.denominator);
+        let (left, right) = (self.denominator / divisor, other.denominator / divisor);
+        Rational::new(
+            self.numerator * right + other.numerator * left,
+            self.denominator * right,
+        )
+    }
+}
+
+impl<T: Signed> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + -other
+    }
+}
+
+impl<T: Signed> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        // cancel across the fractions first to keep the intermediate values small
+        let first = gcd(self.numerator, other.denominator);
+        let second = gcd(other.numerator, self.denominator);
+        Rational::new(
+            (self.numerator / first) * (other.numerator / second),
+            (self.denominator / second) * (other.denominator / first),
+        )
+    }
+}
+
+impl<T: Signed> Div for Rational<T> {
+    type Output = Self;
+
+    /**
+     * Panics when dividing by zero
+     */
+    fn div(self, other: Self) -> Self::Output {
+        let reciprocal = other
+            .recip()
+            .unwrap_or_else(|| panic!("Attempted to divide {} by zero", self));
+        self.mul(reciprocal)
+    }
+}
+
+impl<T: Signed> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Rational {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+macro_rules! impl_assign {
+    ($($trait:ident $method:ident $op:tt),*) => {
+        $(
+            impl<T: Signed> $trait for Rational<T> {
+                fn $method(&mut self, other: Self) {
+                    *self = *self $op other;
+                }
+            }
+        )*
+    };
+}
+
+impl_assign!(AddAssign add_assign +, SubAssign sub_assign -, MulAssign mul_assign *, DivAssign div_assign /);
+
+impl<T: Signed> Ord for Rational<T> {
+    /**
+     * Compares integer parts first and then the reciprocals of the fractional parts, like
+     * comparing continued fractions, so no cross multiplication can overflow
+     */
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b) = (*self, *other);
+        let mut reversed = false;
+
+        loop {
+            let ordering = a.floor().cmp(&b.floor()).then_with(|| {
+                let (fraction_a, fraction_b) = (a.fraction(), b.fraction());
+                match (fraction_a.recip(), fraction_b.recip()) {
+                    (None, None) => Ordering::Equal,
+                    (None, Some(_)) => Ordering::Less,
+                    (Some(_), None) => Ordering::Greater,
+                    (Some(next_a), Some(next_b)) => {
+                        // a larger fraction has a smaller reciprocal
+                        a = next_a;
+                        b = next_b;
+                        reversed = !reversed;
+                        Ordering::Equal
+                    }
+                }
+            });
+
+            if ordering != Ordering::Equal || a == b {
+                return if reversed {
+                    ordering.reverse()
+                } else {
+                    ordering
+                };
+            }
+        }
+    }
+}
+
+impl<T: Signed> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Rational {
+                fn from(value: $t) -> Self {
+                    Rational::integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+macro_rules! impl_try_into_integer {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<Rational> for $t {
+                type Error = RationalToIntegerError;
+
+                fn try_from(value: Rational) -> Result<Self, Self::Error> {
+                    let integer = value
+                        .to_integer()
+                        .ok_or(RationalToIntegerError::Fraction)?;
+                    <$t>::try_from(integer).map_err(|_| RationalToIntegerError::OutOfRange)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_into_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RationalToIntegerError {
+    Fraction,
+    OutOfRange,
+}
+
+impl Display for RationalToIntegerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RationalToIntegerError::Fraction => write!(f, "the value has a fractional part"),
+            RationalToIntegerError::OutOfRange => {
+                write!(f, "the value does not fit in the integer type")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RationalToIntegerError {}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseRationalError {
+    found: String,
+}
+
+impl Display for ParseRationalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a fraction, expected an integer or a/b with b non-zero",
+            self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseRationalError {}
+
+impl<T: Signed + FromStr> FromStr for Rational<T> {
+    type Err = ParseRationalError;
+
+    /**
+     * Parses "a/b" or a plain integer "a", allowing whitespace around the numbers
+     */
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseRationalError {
+            found: s.to_owned(),
+        };
+        let (numerator, denominator) = s.split_once('/').unwrap_or((s, "1"));
+        let numerator = numerator.trim().parse::<T>().map_err(|_| error())?;
+        let denominator = denominator.trim().parse::<T>().map_err(|_| error())?;
+
+        if denominator == T::ZERO {
+            Err(error())
+        } else {
+            Ok(Rational::new(numerator, denominator))
+        }
+    }
+}
+
+impl<T: Signed> Display for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num::{rational::RationalToIntegerError, Rational};
+
+    #[test]
+    fn normalise_test() {
+        let half = Rational::new(-3, -6);
+        assert_eq!((1, 2), (half.numerator(), half.denominator()));
+        assert_eq!(Rational::new(-1, 2), Rational::new(2, -4));
+        assert_eq!(Rational::zero(), Rational::new(0, -5));
+        assert_eq!(Some(3), Rational::new(9, 3).to_integer());
+        assert_eq!(None, half.to_integer());
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let (a, b) = (Rational::new(1, 6), Rational::new(3, 4));
+
+        assert_eq!(Rational::new(11, 12), a + b);
+        assert_eq!(Rational::new(-7, 12), a - b);
+        assert_eq!(Rational::new(1, 8), a * b);
+        assert_eq!(Rational::new(2, 9), a / b);
+        assert_eq!("-7/12", (a - b).to_string());
+        assert_eq!("2", (Rational::from(4) / Rational::from(2)).to_string());
+
+        let mut value = Rational::from(3u8);
+        value += Rational::new(1, 2);
+        value *= Rational::new(-2, 1);
+        value -= Rational::from(1i64);
+        value /= Rational::new(4, 3);
+        assert_eq!(Rational::new(-6, 1), value);
+        assert_eq!(None, Rational::<i128>::zero().recip());
+    }
+
+    #[test]
+    fn ordering_test() {
+        let mut values: Vec<Rational> = vec![
+            Rational::new(1, 3),
+            Rational::new(-1, 2),
+            Rational::from(2),
+            Rational::new(1, 2),
+            Rational::new(-2, 3),
+            Rational::new(2, 6),
+        ];
+        values.sort();
+        assert_eq!(
+            vec![
+                Rational::new(-2, 3),
+                Rational::new(-1, 2),
+                Rational::new(1, 3),
+                Rational::new(1, 3),
+                Rational::new(1, 2),
+                Rational::from(2),
+            ],
+            values
+        );
+
+        // cross multiplying these would overflow an i128
+        let big = i128::MAX;
+        assert!(Rational::new(big - 1, big) < Rational::new(big - 2, big - 1).recip().unwrap());
+        assert!(Rational::new(big - 2, big - 1) < Rational::new(big - 1, big));
+        assert!(Rational::new(-big, big - 1) > Rational::new(-big + 1, big - 2));
+    }
+
+    #[test]
+    fn rounding_test() {
+        assert_eq!(
+            (2, 3),
+            (Rational::new(7, 3).floor(), Rational::new(7, 3).ceil())
+        );
+        assert_eq!(
+            (-3, -2),
+            (Rational::new(-7, 3).floor(), Rational::new(-7, 3).ceil())
+        );
+        assert_eq!(
+            (4, 4),
+            (Rational::integer(4).floor(), Rational::integer(4).ceil())
+        );
+        assert_eq!(Rational::new(7, 3), Rational::new(-7, 3).abs());
+    }
+
+    #[test]
+    fn conversion_test() {
+        assert_eq!(Ok(-4i64), i64::try_from(Rational::new(8, -2)));
+        assert_eq!(
+            Err(RationalToIntegerError::Fraction),
+            i64::try_from(Rational::new(1, 2))
+        );
+        assert_eq!(
+            Err(RationalToIntegerError::OutOfRange),
+            u8::try_from(Rational::from(-1))
+        );
+        assert_eq!(
+            Err(RationalToIntegerError::OutOfRange),
+            i64::try_from(Rational::integer(i128::MAX))
+        );
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(Ok(Rational::new(-3, 4)), "-6/8".parse());
+        assert_eq!(Ok(Rational::new(3, 4)), " 3 / 4 ".parse());
+        assert_eq!(Ok(Rational::from(12)), "12".parse());
+        assert_eq!(Ok(Rational::<i64>::new(1, -2)), "1/-2".parse());
+        assert!("1/0".parse::<Rational>().is_err());
+        assert!("1/2/3".parse::<Rational>().is_err());
+        assert!("a/b".parse::<Rational>().is_err());
+        assert_eq!(
+            "'x' is not a fraction, expected an integer or a/b with b non-zero",
+            "x".parse::<Rational>().unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,423 @@
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/**
+ * A position in the text being parsed. Parsers only ever move forward, but keep the whole source
+ * around so errors can report the line and column they happened at.
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Input<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Input { source, offset: 0 }
+    }
+
+    pub(crate) fn at(source: &'a str, offset: usize) -> Self {
+        Input { source, offset }
+    }
+
+    /**
+     * The text that has not been consumed yet
+     */
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    fn advance(&self, bytes: usize) -> Self {
+        Input {
+            source: self.source,
+            offset: self.offset + bytes,
+        }
+    }
+
+    /**
+     * An error at this position, listing what would have been accepted here
+     */
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let consumed = &self.source[..self.offset];
+        let line = consumed.matches('\n').count() + 1;
+        let line_start = consumed.rfind('\n').map_or(0, |index| index + 1);
+        let column = consumed[line_start..].chars().count() + 1;
+        let snippet = self.source[line_start..].lines().next().unwrap_or("");
+
+        ParseError::new(line, column, snippet, expected)
+    }
+}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+/**
+ * Anything that can consume the start of an input, which includes every function or closure
+ * from Input to ParseResult
+ */
+pub trait Parser<'a, T> {
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+/**
+ * Runs the parser on the whole text, allowing only whitespace after it
+ */
+pub fn parse_all<'a, T>(parser: impl Parser<'a, T>, text: &'a str) -> Result<T, ParseError> {
+    let (value, rest) = parser.parse(Input::new(text))?;
+    let ((), rest) = space(rest)?;
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(rest.error("end of input"))
+    }
+}
+
+/**
+ * Exactly the given text
+ */
+pub fn tag<'a>(expected: &'static str) -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    move |input: Input<'a>| {
+        if input.rest().starts_with(expected) {
+            Ok((
+                &input.rest()[..expected.len()],
+                input.advance(expected.len()),
+            ))
+        } else {
+            Err(input.error(format!("'{}'", expected)))
+        }
+    }
+}
+
+/**
+ * Zero or more whitespace characters, including newlines
+ */
+pub fn space(input: Input<'_>) -> ParseResult<'_, ()> {
+    let rest = input.rest();
+    let length = rest.len() - rest.trim_start().len();
+    Ok(((), input.advance(length)))
+}
+
+/**
+ * A decimal integer with an optional leading '-', which has to fit in T
+ */
+pub fn integer<'a, T: FromStr>() -> impl Fn(Input<'a>) -> ParseResult<'a, T> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let sign = usize::from(rest.starts_with('-'));
+        let digits = rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return Err(input.error("an integer"));
+        }
+        let length = sign + digits;
+        rest[..length]
+            .parse()
+            .map(|value| (value, input.advance(length)))
+            .map_err(|_| input.error(format!("an integer fitting in {}", short_type_name::<T>())))
+    }
+}
+
+pub(crate) fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/**
+ * The current position without consuming anything, so a value can still be reported at the place
+ * it was parsed from once the whole input has been checked
+ */
+pub fn position(input: Input<'_>) -> ParseResult<'_, Input<'_>> {
+    Ok((input, input))
+}
+
+/**
+ * One or more letters, digits or underscores
+ */
+pub fn word(input: Input<'_>) -> ParseResult<'_, &str> {
+    let rest = input.rest();
+    let length = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    if length == 0 {
+        Err(input.error("a word"))
+    } else {
+        Ok((&rest[..length], input.advance(length)))
+    }
+}
+
+/**
+ * One or more characters matching the predicate
+ */
+pub fn take_while1<'a>(
+    predicate: impl Fn(char) -> bool,
+    expected: &'static str,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        if length == 0 {
+            Err(input.error(expected))
+        } else {
+            Ok((&rest[..length], input.advance(length)))
+        }
+    }
+}
+
+pub fn map<'a, A, B>(
+    parser: impl Parser<'a, A>,
+    f: impl Fn(A) -> B,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, B> {
+    move |input: Input<'a>| parser.parse(input).map(|(value, rest)| (f(value), rest))
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, (A, B)> {
+    move |input: Input<'a>| {
+        let (a, rest) = first.parse(input)?;
+        let (b, rest) = second.parse(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+/**
+ * Runs both parsers and keeps the value of the second
+ */
+pub fn preceded<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+/**
+ * Runs both parsers and keeps the value of the first
+ */
+pub fn terminated<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+pub fn delimited<'a, A, B, C>(
+    open: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+    close: impl Parser<'a, C>,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, B> {
+    preceded(open, terminated(parser, close))
+}
+
+/**
+ * The parser with any whitespace around it skipped
+ */
+pub fn trim<'a, T>(parser: impl Parser<'a, T>) -> impl Fn(Input<'a>) -> ParseResult<'a, T> {
+    delimited(space, parser, space)
+}
+
+/**
+ * One or more items with a separator between each pair
+ */
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut rest) = item.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((_, next)) = separator.parse(rest) {
+            let (value, next) = item.parse(next)?;
+            items.push(value);
+            rest = next;
+        }
+        Ok((items, rest))
+    }
+}
+
+/**
+ * The parser applied as often as it succeeds, at least once. Unlike separated this never fails
+ * after the first item, it just stops, so whatever follows has to be parsed by the next parser.
+ */
+pub fn many1<'a, T>(parser: impl Parser<'a, T>) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut rest) = parser.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((value, next)) = parser.parse(rest) {
+            items.push(value);
+            rest = next;
+        }
+        Ok((items, rest))
+    }
+}
+
+/**
+ * A tuple of parsers that are tried in order, see alt
+ */
+pub trait Alternatives<'a, T> {
+    fn choose(&self, input: Input<'a>) -> ParseResult<'a, T>;
+}
+
+macro_rules! impl_alternatives {
+    ($($parser:ident $index:tt),+) => {
+        impl<'a, T, $($parser: Parser<'a, T>),+> Alternatives<'a, T> for ($($parser,)+) {
+            fn choose(&self, input: Input<'a>) -> ParseResult<'a, T> {
+                let mut error: Option<ParseError> = None;
+                $(
+                    match self.$index.parse(input) {
+                        Ok(result) => return Ok(result),
+                        Err(e) => error = Some(furthest(error, e)),
+                    }
+                )+
+                Err(error.unwrap())
+            }
+        }
+    };
+}
+
+impl_alternatives!(A 0, B 1);
+impl_alternatives!(A 0, B 1, C 2);
+impl_alternatives!(A 0, B 1, C 2, D 3);
+impl_alternatives!(A 0, B 1, C 2, D 3, E 4);
+impl_alternatives!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+/**
+ * The first of the parsers that succeeds. When all fail, the error comes from the one that got
+ * furthest, and alternatives failing at the same position are listed together.
+ */
+pub fn alt<'a, T>(
+    alternatives: impl Alternatives<'a, T>,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, T> {
+    move |input: Input<'a>| alternatives.choose(input)
+}
+
+fn furthest(previous: Option<ParseError>, error: ParseError) -> ParseError {
+    match previous {
+        None => error,
+        Some(previous) => match (previous.line, previous.column).cmp(&(error.line, error.column)) {
+            std::cmp::Ordering::Greater => previous,
+            std::cmp::Ordering::Less => error,
+            std::cmp::Ordering::Equal => ParseError {
+                expected: format!("{} or {}", previous.expected, error.expected),
+                ..error
+            },
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        error::ParseError,
+        parse::{
+            alt, delimited, integer, many1, map, pair, parse_all, position, preceded, separated,
+            tag, take_while1, trim, word,
+        },
+    };
+
+    #[test]
+    fn primitives_test() {
+        assert_eq!(Ok("Game"), parse_all(tag("Game"), "Game"));
+        assert_eq!(Ok(-42), parse_all(integer::<i32>(), "-42"));
+        assert_eq!(Ok("AAA"), parse_all(trim(word), "  AAA \n"));
+        assert_eq!(
+            Ok(("?#.", 4)),
+            parse_all(
+                pair(
+                    take_while1(|c| ".#?".contains(c), "springs"),
+                    preceded(tag(" "), integer::<u8>())
+                ),
+                "?#. 4"
+            )
+        );
+        assert_eq!(
+            Ok(("AAA", ("BBB", "CCC"))),
+            parse_all(
+                pair(
+                    word,
+                    preceded(
+                        trim(tag("=")),
+                        delimited(tag("("), pair(word, preceded(tag(", "), word)), tag(")"))
+                    )
+                ),
+                "AAA = (BBB, CCC)"
+            )
+        );
+    }
+
+    #[test]
+    fn separated_test() {
+        let list = separated(trim(integer::<u8>()), tag(","));
+        assert_eq!(Ok(vec![1, 2, 3]), parse_all(&list, "1, 2 ,3"));
+        assert_eq!(Ok(vec![7]), parse_all(&list, "7"));
+
+        let card = pair(
+            many1(trim(integer::<u8>())),
+            preceded(tag("|"), many1(trim(integer::<u8>()))),
+        );
+        assert_eq!(
+            Ok((vec![41, 48], vec![83])),
+            parse_all(&card, " 41 48 | 83")
+        );
+        assert_eq!("'|'", parse_all(&card, " 41 x | 83").unwrap_err().expected);
+    }
+
+    #[test]
+    fn alt_test() {
+        let color = alt((
+            map(tag("red"), |_| 'r'),
+            map(tag("green"), |_| 'g'),
+            map(tag("blue"), |_| 'b'),
+        ));
+        assert_eq!(
+            Ok(vec!['g', 'r']),
+            parse_all(separated(&color, tag(" ")), "green red")
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                7,
+                "green purple",
+                "'red' or 'green' or 'blue'"
+            )),
+            parse_all(separated(&color, tag(" ")), "green purple")
+        );
+    }
+
+    #[test]
+    fn error_position_test() {
+        let rows = separated(separated(integer::<u32>(), tag(" ")), tag("\n"));
+        let error = parse_all(rows, "1 2\n3 x 4").unwrap_err();
+
+        assert_eq!((2, 3), (error.line, error.column));
+        assert_eq!(
+            "line 2, column 3: expected an integer, found 'x 4'",
+            error.to_string()
+        );
+
+        let error = parse_all(integer::<u8>(), "300").unwrap_err();
+        assert_eq!("an integer fitting in u8", error.expected);
+
+        let error = parse_all(tag("Game"), "Game 1").unwrap_err();
+        assert_eq!("end of input", error.expected);
+        assert_eq!(6, error.column);
+
+        let (at, _) = parse_all(pair(preceded(tag("é\n→ "), position), word), "é\n→ node").unwrap();
+        assert_eq!(
+            ParseError::new(2, 3, "→ node", "a known node"),
+            at.error("a known node")
+        );
+    }
+}
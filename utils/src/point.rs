@@ -1,11 +1,32 @@
-use std::ops::{Add, Sub};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::num::Integer;
 
 pub const ORIGIN: Point = Point::new(0, 0);
 
+const ORTHOGONAL: [Point; 4] = [
+    Point::new(0, -1),
+    Point::new(0, 1),
+    Point::new(-1, 0),
+    Point::new(1, 0),
+];
+
+const DIAGONAL: [Point; 4] = [
+    Point::new(-1, -1),
+    Point::new(1, -1),
+    Point::new(-1, 1),
+    Point::new(1, 1),
+];
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub struct Point {
-    pub x: i64,
-    pub y: i64,
+pub struct Point<T = i64> {
+    pub x: T,
+    pub y: T,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
@@ -15,6 +36,233 @@ pub enum Direction {
     LEFT,
     RIGHT,
 }
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseDirectionError {
+    found: String,
+}
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a direction, expected one of U/D/L/R, ^/v/</> or N/S/W/E",
+            self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+impl Direction {
+    pub const fn all() -> [Direction; 4] {
+        [
+            Direction::UP,
+            Direction::RIGHT,
+            Direction::DOWN,
+            Direction::LEFT,
+        ]
+    }
+
+    /**
+     * The point one step in this direction from the origin, with y growing downwards
+     */
+    pub const fn unit(self) -> Point {
+        match self {
+            Direction::UP => Point::new(0, -1),
+            Direction::DOWN => Point::new(0, 1),
+            Direction::LEFT => Point::new(-1, 0),
+            Direction::RIGHT => Point::new(1, 0),
+        }
+    }
+
+    /**
+     * Rotate 90 degrees counterclockwise
+     */
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Direction::UP => Direction::LEFT,
+            Direction::LEFT => Direction::DOWN,
+            Direction::DOWN => Direction::RIGHT,
+            Direction::RIGHT => Direction::UP,
+        }
+    }
+
+    /**
+     * Rotate 90 degrees clockwise
+     */
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Direction::UP => Direction::RIGHT,
+            Direction::RIGHT => Direction::DOWN,
+            Direction::DOWN => Direction::LEFT,
+            Direction::LEFT => Direction::UP,
+        }
+    }
+
+    pub const fn opposite(self) -> Self {
+        match self {
+            Direction::UP => Direction::DOWN,
+            Direction::DOWN => Direction::UP,
+            Direction::LEFT => Direction::RIGHT,
+            Direction::RIGHT => Direction::LEFT,
+        }
+    }
+
+    /**
+     * The direction after bouncing off a '/' mirror
+     */
+    pub const fn reflect_slash(self) -> Self {
+        match self {
+            Direction::UP => Direction::RIGHT,
+            Direction::RIGHT => Direction::UP,
+            Direction::DOWN => Direction::LEFT,
+            Direction::LEFT => Direction::DOWN,
+        }
+    }
+
+    /**
+     * The direction after bouncing off a '\' mirror
+     */
+    pub const fn reflect_backslash(self) -> Self {
+        match self {
+            Direction::UP => Direction::LEFT,
+            Direction::LEFT => Direction::UP,
+            Direction::DOWN => Direction::RIGHT,
+            Direction::RIGHT => Direction::DOWN,
+        }
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        direction.unit()
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | '^' | 'N' => Ok(Direction::UP),
+            'D' | 'v' | 'S' => Ok(Direction::DOWN),
+            'L' | '<' | 'W' => Ok(Direction::LEFT),
+            'R' | '>' | 'E' => Ok(Direction::RIGHT),
+            _ => Err(ParseDirectionError {
+                found: c.to_string(),
+            }),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(ParseDirectionError {
+                found: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Direction::UP => 'U',
+            Direction::DOWN => 'D',
+            Direction::LEFT => 'L',
+            Direction::RIGHT => 'R',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Direction8 {
+    NORTH,
+    NORTHEAST,
+    EAST,
+    SOUTHEAST,
+    SOUTH,
+    SOUTHWEST,
+    WEST,
+    NORTHWEST,
+}
+
+impl Direction8 {
+    /**
+     * All directions in clockwise order, starting from north
+     */
+    pub const fn all() -> [Direction8; 8] {
+        [
+            Direction8::NORTH,
+            Direction8::NORTHEAST,
+            Direction8::EAST,
+            Direction8::SOUTHEAST,
+            Direction8::SOUTH,
+            Direction8::SOUTHWEST,
+            Direction8::WEST,
+            Direction8::NORTHWEST,
+        ]
+    }
+
+    /**
+     * The point one step in this direction from the origin, with north pointing towards negative y
+     */
+    pub const fn unit(self) -> Point {
+        match self {
+            Direction8::NORTH => Point::new(0, -1),
+            Direction8::NORTHEAST => Point::new(1, -1),
+            Direction8::EAST => Point::new(1, 0),
+            Direction8::SOUTHEAST => Point::new(1, 1),
+            Direction8::SOUTH => Point::new(0, 1),
+            Direction8::SOUTHWEST => Point::new(-1, 1),
+            Direction8::WEST => Point::new(-1, 0),
+            Direction8::NORTHWEST => Point::new(-1, -1),
+        }
+    }
+
+    /**
+     * Rotate 45 degrees counterclockwise
+     */
+    pub fn turn_left(self) -> Self {
+        Direction8::all()[(self as usize + 7) % 8]
+    }
+
+    /**
+     * Rotate 45 degrees clockwise
+     */
+    pub fn turn_right(self) -> Self {
+        Direction8::all()[(self as usize + 1) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction8::all()[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::UP => Direction8::NORTH,
+            Direction::DOWN => Direction8::SOUTH,
+            Direction::LEFT => Direction8::WEST,
+            Direction::RIGHT => Direction8::EAST,
+        }
+    }
+}
+
+impl From<Direction8> for Point {
+    fn from(direction: Direction8) -> Self {
+        direction.unit()
+    }
+}
+
 pub trait Directional {
     fn up(self) -> (Self, Direction)
     where
@@ -70,42 +318,319 @@ impl Directional for Point {
     where
         Self: Sized,
     {
-        match direction {
-            Direction::UP => self.up(),
-            Direction::DOWN => self.down(),
-            Direction::LEFT => self.left(),
-            Direction::RIGHT => self.right(),
-        }
+        (self + direction.unit(), *direction)
     }
 }
 
-impl Point {
-    pub const fn new(x: i64, y: i64) -> Self {
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
         Point { x, y }
     }
+}
+
+impl<T: Integer> Point<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /**
+     * The squared euclidean distance, which stays exact for integers
+     */
+    pub fn distance_squared(self, other: Self) -> T {
+        let dx = self.x.distance(other.x);
+        let dy = self.y.distance(other.y);
+        dx * dx + dy * dy
+    }
+
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /**
+     * The z component of the 3D cross product, positive when other is clockwise of self
+     * (with y growing downwards)
+     */
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
 
-    pub fn manhattan(self, other: Self) -> i64 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
+    /**
+     * Converts to (x, y) grid indices, failing for coordinates that do not fit in a usize
+     */
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+
+    /**
+     * Converts to another integer type, failing if either coordinate does not fit
+     */
+    pub fn cast<U: Integer>(self) -> Option<Point<U>>
+    where
+        T: TryInto<U>,
+    {
+        Some(Point::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> Point<T> {
+    /**
+     * Rotate a quarter turn clockwise around the origin, with y growing downwards
+     */
+    pub fn rotate90_cw(self) -> Self {
+        Point::new(-self.y, self.x)
+    }
+
+    /**
+     * Rotate a quarter turn counterclockwise around the origin, with y growing downwards
+     */
+    pub fn rotate90_ccw(self) -> Self {
+        Point::new(self.y, -self.x)
+    }
+
+    /**
+     * Clamps each coordinate to -1, 0 or 1, e.g. to step one cell towards another point
+     */
+    pub fn signum(self) -> Self {
+        let signum = |v: T| match v.cmp(&T::ZERO) {
+            Ordering::Less => -T::ONE,
+            Ordering::Equal => T::ZERO,
+            Ordering::Greater => T::ONE,
+        };
+        Point::new(signum(self.x), signum(self.y))
+    }
+}
+
+impl Point {
+    /**
+     * Returns the points directly above, below, left and right of this point
+     */
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        ORTHOGONAL.into_iter().map(move |offset| self + offset)
+    }
+
+    /**
+     * Returns the orthogonal neighbours followed by the diagonal ones
+     */
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        ORTHOGONAL
+            .into_iter()
+            .chain(DIAGONAL)
+            .map(move |offset| self + offset)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
     }
 }
 
-impl Add for Point {
+/**
+ * Grid coordinates as (x, y). Any in-memory grid index fits within an i64.
+ */
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as i64, y as i64)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
     type Output = Self;
     fn add(self, other: Self) -> Self {
         Point::new(self.x + other.x, self.y + other.y)
     }
 }
 
-impl Sub for Point {
+impl<T: Sub<Output = T>> Sub for Point<T> {
     type Output = Self;
     fn sub(self, other: Self) -> Self {
         Point::new(self.x - other.x, self.y - other.y)
     }
 }
 
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+    fn mul(self, scalar: T) -> Self {
+        Point::new(self.x * scalar, self.y * scalar)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Point<T> {
+    type Output = Self;
+    fn div(self, scalar: T) -> Self {
+        Point::new(self.x / scalar, self.y / scalar)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Point3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Integer> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x
+            .distance(other.x)
+            .max(self.y.distance(other.y))
+            .max(self.z.distance(other.z))
+    }
+
+    /**
+     * Converts to (x, y, z) grid indices, failing for coordinates that do not fit in a usize
+     */
+    pub fn to_index(self) -> Option<(usize, usize, usize)> {
+        Some((
+            self.x.try_into().ok()?,
+            self.y.try_into().ok()?,
+            self.z.try_into().ok()?,
+        ))
+    }
+
+    /**
+     * Converts to another integer type, failing if any coordinate does not fit
+     */
+    pub fn cast<U: Integer>(self) -> Option<Point3<U>>
+    where
+        T: TryInto<U>,
+    {
+        Some(Point3::new(
+            self.x.try_into().ok()?,
+            self.y.try_into().ok()?,
+            self.z.try_into().ok()?,
+        ))
+    }
+}
+
+impl Point3 {
+    /**
+     * Returns the points sharing a face with this point
+     */
+    pub fn neighbours6(self) -> impl Iterator<Item = Point3> {
+        [
+            Point3::new(-1, 0, 0),
+            Point3::new(1, 0, 0),
+            Point3::new(0, -1, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, 0, -1),
+            Point3::new(0, 0, 1),
+        ]
+        .into_iter()
+        .map(move |offset| self + offset)
+    }
+
+    /**
+     * Returns the points sharing a face, edge or corner with this point
+     */
+    pub fn neighbours26(self) -> impl Iterator<Item = Point3> {
+        (-1..=1)
+            .flat_map(|dz| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dx| (dx, dy, dz))))
+            .filter(|&offset| offset != (0, 0, 0))
+            .map(move |(dx, dy, dz)| self + Point3::new(dx, dy, dz))
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+    fn mul(self, scalar: T) -> Self {
+        Point3::new(self.x * scalar, self.y * scalar, self.z * scalar)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::point::{Point, ORIGIN};
+    use crate::point::{Direction, Direction8, Point, Point3, ORIGIN};
 
     #[test]
     fn manhattan_distance_test() {
@@ -135,4 +660,150 @@ mod test {
         assert_eq!(Point::new(-3, 0), ORIGIN - Point::new(3, 0));
         assert_eq!(Point::new(-1, -9), Point::new(-1, -5) - Point::new(0, 4));
     }
+
+    #[test]
+    fn neighbours_test() {
+        let point = Point::new(2, -3);
+
+        assert_eq!(4, point.neighbours4().count());
+        assert!(point.neighbours4().all(|n| n.manhattan(point) == 1));
+        assert_eq!(8, point.neighbours8().count());
+        assert!(!point.neighbours8().any(|n| n == point));
+        assert_eq!(
+            point.neighbours4().collect::<Vec<_>>(),
+            point.neighbours8().take(4).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn direction_turn_test() {
+        for direction in Direction::all() {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(direction.opposite(), direction.turn_right().turn_right());
+            assert_eq!(direction, direction.reflect_slash().reflect_slash());
+            assert_eq!(direction, direction.reflect_backslash().reflect_backslash());
+            assert_eq!(ORIGIN, direction.unit() + direction.opposite().unit());
+        }
+
+        assert_eq!(Direction::RIGHT, Direction::UP.turn_right());
+        assert_eq!(Direction::UP, Direction::RIGHT.reflect_slash());
+        assert_eq!(Direction::DOWN, Direction::RIGHT.reflect_backslash());
+        assert_eq!(Point::new(0, -1), Point::from(Direction::UP));
+    }
+
+    #[test]
+    fn direction_parse_test() {
+        for direction in Direction::all() {
+            assert_eq!(Ok(direction), direction.to_string().parse());
+        }
+
+        assert_eq!(Ok(Direction::UP), "^".parse());
+        assert_eq!(Ok(Direction::DOWN), Direction::try_from('v'));
+        assert_eq!(Ok(Direction::LEFT), "W".parse());
+        assert_eq!(Ok(Direction::RIGHT), ">".parse());
+        assert!("X".parse::<Direction>().is_err());
+        assert!("UD".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn direction8_test() {
+        for direction in Direction8::all() {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(
+                direction.opposite(),
+                direction.turn_right().opposite().turn_left()
+            );
+            assert_eq!(ORIGIN, direction.unit() + direction.opposite().unit());
+        }
+
+        assert_eq!(Direction8::NORTHEAST, Direction8::NORTH.turn_right());
+        assert_eq!(Direction8::NORTHWEST, Direction8::NORTH.turn_left());
+        for direction in Direction::all() {
+            assert_eq!(direction.unit(), Direction8::from(direction).unit());
+        }
+    }
+
+    #[test]
+    fn conversion_test() {
+        assert_eq!(Point::new(3i64, 4), Point::from((3usize, 4usize)));
+        assert_eq!(Point::new(-3i32, 4), Point::from((-3, 4)));
+        assert_eq!((-3i32, 4), Point::new(-3, 4).into());
+        assert_eq!(Some((3, 4)), Point::new(3, 4).to_index());
+        assert_eq!(None, Point::new(-1, 4).to_index());
+        assert_eq!(Some(Point::new(-3i32, 4)), Point::new(-3i64, 4).cast());
+        assert_eq!(None, Point::new(i64::MAX, 0).cast::<i32>());
+        assert_eq!(None, Point::new(-1i8, 0).cast::<u8>());
+    }
+
+    #[test]
+    fn generic_point_test() {
+        assert_eq!(7u8, Point::new(1u8, 5).manhattan(Point::new(4, 1)));
+        assert_eq!(4u8, Point::new(1u8, 5).chebyshev(Point::new(4, 1)));
+        assert_eq!(Point::new(3i32, 3), Point::new(1, 2) + Point::new(2, 1));
+        assert_eq!(3, ORIGIN.chebyshev(Point::new(-3, 2)));
+    }
+
+    #[test]
+    fn vector_arithmetic_test() {
+        let a = Point::new(3, -4);
+
+        assert_eq!(Point::new(6, -8), a * 2);
+        assert_eq!(Point::new(1, -2), a / 2);
+        assert_eq!(Point::new(-3, 4), -a);
+        assert_eq!(Point::new(1, -1), a.signum());
+        assert_eq!(ORIGIN, ORIGIN.signum());
+        assert_eq!(25, ORIGIN.distance_squared(a));
+        assert_eq!(-5, a.dot(Point::new(1, 2)));
+        assert_eq!(10, a.cross(Point::new(1, 2)));
+
+        let mut b = a;
+        b += Point::new(1, 1);
+        assert_eq!(Point::new(4, -3), b);
+        b -= Point::new(1, 1);
+        assert_eq!(a, b);
+
+        assert_eq!(
+            Point::new(0, 0) + Direction::RIGHT.unit() * 5,
+            Point::new(5, 0)
+        );
+    }
+
+    #[test]
+    fn rotation_test() {
+        for direction in Direction::all() {
+            assert_eq!(
+                direction.turn_right().unit(),
+                direction.unit().rotate90_cw()
+            );
+            assert_eq!(
+                direction.turn_left().unit(),
+                direction.unit().rotate90_ccw()
+            );
+        }
+
+        let a = Point::new(3, -4);
+        assert_eq!(a, a.rotate90_cw().rotate90_ccw());
+        assert_eq!(-a, a.rotate90_cw().rotate90_cw());
+    }
+
+    #[test]
+    fn point3_test() {
+        let a = Point3::new(1, -2, 3);
+        let b = Point3::new(-1, 2, 0);
+
+        assert_eq!(Point3::new(0, 0, 3), a + b);
+        assert_eq!(Point3::new(2, -4, 3), a - b);
+        assert_eq!(Point3::new(2, -4, 6), a * 2);
+        assert_eq!(Point3::new(-1, 2, -3), -a);
+        assert_eq!(9, a.manhattan(b));
+        assert_eq!(4, a.chebyshev(b));
+
+        let mut c = a;
+        c += b;
+        assert_eq!(a + b, c);
+
+        assert_eq!(Some((1, 2, 3)), Point3::new(1u8, 2, 3).to_index());
+        assert_eq!(None, a.to_index());
+        assert_eq!(Some(Point3::new(1i16, -2, 3)), a.cast());
+    }
 }
@@ -0,0 +1,290 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::{error::ParseError, parse::short_type_name};
+
+/**
+ * Parses a line against a pattern where every `{}` is a field, converting the fields with
+ * `FromStr` into the listed types:
+ *
+ * ```
+ * use utils::scan;
+ *
+ * let (from, left, right) = scan!("AAA = (BBB, CCC)", "{} = ({}, {})" => String, String, String)?;
+ * assert_eq!(("AAA", "BBB", "CCC"), (&*from, &*left, &*right));
+ * # Ok::<(), utils::scan::ScanError>(())
+ * ```
+ *
+ * Each field runs up to the first occurrence of the text following it in the pattern, and
+ * whitespace around fields is ignored, so "Card {}: {}" also matches "Card   3: 1 2".
+ * A field scanned as `Located<T>` also keeps the column it starts at, for reporting problems found
+ * in it later. Panics when the number of `{}` does not match the number of types.
+ */
+#[macro_export]
+macro_rules! scan {
+    ($text:expr, $pattern:literal => $($target:ty),+ $(,)?) => {
+        (|| -> ::std::result::Result<_, $crate::scan::ScanError> {
+            let text: &str = &$text;
+            let count = [$(stringify!($target)),+].len();
+            let mut fields = $crate::scan::fields(text, $pattern, count)?.into_iter().enumerate();
+            Ok(($({
+                let (index, (column, field)) = fields.next().unwrap();
+                <$target as $crate::scan::FromField>::from_field(text, index, column, field)?
+            },)+))
+        })()
+    };
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ScanErrorKind {
+    /**
+     * The text around the fields did not match the pattern at the given column (from 1)
+     */
+    MissingLiteral { literal: String, column: usize },
+    /**
+     * A field (counted from 0) starting at the given column could not be converted to its type
+     */
+    InvalidField {
+        index: usize,
+        column: usize,
+        field: String,
+        target: &'static str,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ScanError {
+    pub line: String,
+    pub kind: ScanErrorKind,
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot scan '{}': ", self.line)?;
+        match &self.kind {
+            ScanErrorKind::MissingLiteral { literal, column } => {
+                write!(f, "expected '{}' at column {}", literal, column)
+            }
+            ScanErrorKind::InvalidField {
+                index,
+                field,
+                target,
+                ..
+            } => write!(
+                f,
+                "field {} '{}' is not a valid {}",
+                index + 1,
+                field,
+                target
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+impl From<ScanError> for ParseError {
+    /**
+     * A scan only sees a single line, so the error is on line 1, see ParseError::offset_lines
+     */
+    fn from(error: ScanError) -> Self {
+        match error.kind {
+            ScanErrorKind::MissingLiteral { literal, column } => {
+                ParseError::new(1, column, error.line, format!("'{}'", literal))
+            }
+            ScanErrorKind::InvalidField { column, target, .. } => {
+                ParseError::new(1, column, error.line, format!("a {}", target))
+            }
+        }
+    }
+}
+
+/**
+ * A scanned field with the column it starts at, counted from 1 in characters
+ */
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Located<T> {
+    pub column: usize,
+    pub value: T,
+}
+
+/**
+ * The column and trimmed text of every `{}` field in the pattern, used by scan!
+ */
+#[doc(hidden)]
+pub fn fields<'t>(
+    text: &'t str,
+    pattern: &str,
+    count: usize,
+) -> Result<Vec<(usize, &'t str)>, ScanError> {
+    let literals = pattern.split("{}").collect::<Vec<_>>();
+    assert_eq!(
+        count,
+        literals.len() - 1,
+        "Pattern '{}' has {} fields but {} types were given",
+        pattern,
+        literals.len() - 1,
+        count
+    );
+
+    let line = text.trim_end();
+    let missing = |literal: &str, rest: &str| ScanError {
+        line: line.to_owned(),
+        kind: ScanErrorKind::MissingLiteral {
+            literal: literal.to_owned(),
+            column: line[..line.len() - rest.len()].chars().count() + 1,
+        },
+    };
+
+    let mut rest = line
+        .strip_prefix(literals[0])
+        .ok_or_else(|| missing(literals[0], line))?;
+    let mut fields = Vec::with_capacity(count);
+
+    for (index, literal) in literals.iter().enumerate().skip(1) {
+        let end = if index == count {
+            // the last field takes everything up to the closing text of the pattern
+            rest.strip_suffix(literal)
+                .map(str::len)
+                .ok_or_else(|| missing(literal, &rest[rest.len()..]))?
+        } else if literal.is_empty() {
+            panic!(
+                "Pattern '{}' has two fields without text between them",
+                pattern
+            );
+        } else {
+            rest.find(literal).ok_or_else(|| missing(literal, rest))?
+        };
+        let field = rest[..end].trim_end();
+        let field_start = line.len() - rest.len() + (field.len() - field.trim_start().len());
+        fields.push((line[..field_start].chars().count() + 1, field.trim_start()));
+        rest = &rest[end + literal.len()..];
+    }
+    Ok(fields)
+}
+
+/**
+ * The types scan! converts fields into, with a field and its column returned by fields
+ */
+#[doc(hidden)]
+pub trait FromField: Sized {
+    fn from_field(text: &str, index: usize, column: usize, field: &str) -> Result<Self, ScanError>;
+}
+
+impl<T: FromStr> FromField for T {
+    fn from_field(text: &str, index: usize, column: usize, field: &str) -> Result<Self, ScanError> {
+        field.parse().map_err(|_| ScanError {
+            line: text.trim_end().to_owned(),
+            kind: ScanErrorKind::InvalidField {
+                index,
+                column,
+                field: field.to_owned(),
+                target: short_type_name::<T>(),
+            },
+        })
+    }
+}
+
+impl<T: FromStr> FromField for Located<T> {
+    fn from_field(text: &str, index: usize, column: usize, field: &str) -> Result<Self, ScanError> {
+        let value = T::from_field(text, index, column, field)?;
+        Ok(Located { column, value })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        error::ParseError,
+        scan::{Located, ScanError, ScanErrorKind},
+    };
+
+    #[test]
+    fn scan_test() {
+        assert_eq!(
+            Ok(("AAA".to_owned(), "BBB".to_owned(), "CCC".to_owned())),
+            scan!("AAA = (BBB, CCC)", "{} = ({}, {})" => String, String, String)
+        );
+        assert_eq!(
+            Ok((3u32, "1 2".to_owned(), "4 5".to_owned())),
+            scan!("Card   3: 1 2 | 4 5\r\n", "Card {}: {} | {}" => u32, String, String)
+        );
+        assert_eq!(Ok((-7i64,)), scan!(String::from("x=-7"), "x={}" => i64));
+    }
+
+    #[test]
+    fn located_test() {
+        let (id, numbers) =
+            scan!("Card 1: 1 2", "Card {}: {}" => Located<u32>, Located<String>).unwrap();
+        assert_eq!(
+            Located {
+                column: 6,
+                value: 1
+            },
+            id
+        );
+        assert_eq!(
+            Located {
+                column: 9,
+                value: "1 2".to_owned()
+            },
+            numbers
+        );
+
+        let (_, number) = scan!("Größe:  7", "{}: {}" => String, Located<u8>).unwrap();
+        assert_eq!(9, number.column);
+
+        let error = scan!("p=x", "p={}" => Located<u8>).unwrap_err();
+        assert_eq!(
+            ParseError::new(1, 3, "p=x", "a u8"),
+            ParseError::from(error)
+        );
+    }
+
+    #[test]
+    fn scan_error_test() {
+        let error = scan!("AAA = BBB, CCC)", "{} = ({}, {})" => String, String, String);
+        assert_eq!(
+            Err(ScanError {
+                line: "AAA = BBB, CCC)".to_owned(),
+                kind: ScanErrorKind::MissingLiteral {
+                    literal: " = (".to_owned(),
+                    column: 1,
+                },
+            }),
+            error
+        );
+
+        let error = scan!("Game x: 3 blue", "Game {}: {}" => u32, String).unwrap_err();
+        assert_eq!(
+            "cannot scan 'Game x: 3 blue': field 1 'x' is not a valid u32",
+            error.to_string()
+        );
+
+        let error = scan!("Game  x: 3 blue", "Game {}: {}" => u32, String).unwrap_err();
+        assert_eq!(
+            ParseError::new(1, 7, "Game  x: 3 blue", "a u32"),
+            ParseError::from(error)
+        );
+
+        let error = scan!("Länge:  x", "Länge: {}" => u32).unwrap_err();
+        assert_eq!(
+            ParseError::new(1, 9, "Länge:  x", "a u32"),
+            ParseError::from(error)
+        );
+
+        let error = scan!("p=1,2", "p={},{}>" => u8, u8).unwrap_err();
+        assert_eq!(
+            "cannot scan 'p=1,2': expected '>' at column 6",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn scan_count_mismatch_test() {
+        let _ = scan!("a b", "{} {}" => String);
+    }
+}
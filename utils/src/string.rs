@@ -1,12 +1,17 @@
-use std::{fs::File, io::Read};
+use std::{fmt::Debug, fs::File, io::Read, str::FromStr};
+
+use crate::{
+    error::ParseError,
+    parse::{short_type_name, Input},
+};
 
 pub fn read(file_name: &str) -> Vec<String> {
-    let mut f = File::open(file_name).expect(&format!("file not found: {}", file_name));
+    let mut f = File::open(file_name).unwrap_or_else(|_| panic!("file not found: {}", file_name));
 
     let mut contents = String::new();
 
     f.read_to_string(&mut contents)
-        .expect(&format!("cannot read file {}", file_name));
+        .unwrap_or_else(|_| panic!("cannot read file {}", file_name));
 
     contents
         .trim_end()
@@ -16,16 +21,167 @@ pub fn read(file_name: &str) -> Vec<String> {
 }
 
 pub fn read_string(file_name: &str) -> String {
-    let mut f = File::open(file_name).expect(&format!("file not found: {}", file_name));
+    let mut f = File::open(file_name).unwrap_or_else(|_| panic!("file not found: {}", file_name));
 
     let mut contents = String::new();
 
     f.read_to_string(&mut contents)
-        .expect(&format!("cannot read file {}", file_name));
+        .unwrap_or_else(|_| panic!("cannot read file {}", file_name));
 
     contents.to_string()
 }
 
+/**
+ * The blocks of text separated by one or more blank lines. Line endings are normalised to '\n'
+ * and trailing whitespace is removed from every line, so CRLF files, lines of only spaces and
+ * extra newlines at either end all split the same way as clean input.
+ */
+pub fn sections(text: &str) -> impl Iterator<Item = String> + '_ {
+    numbered_sections(text).map(|(_, section)| section)
+}
+
+/**
+ * The same blocks as sections, each paired with the index of its first line in the text, counting
+ * from 0. Adding it to a line within the block gives the line in the text, which is what
+ * ParseError::offset_lines expects.
+ */
+pub fn numbered_sections(text: &str) -> impl Iterator<Item = (usize, String)> + '_ {
+    let mut lines = text.lines().map(str::trim_end).enumerate().peekable();
+
+    std::iter::from_fn(move || {
+        while lines.next_if(|(_, line)| line.is_empty()).is_some() {}
+
+        let first = lines.peek()?.0;
+        let mut block = Vec::new();
+        while let Some((_, line)) = lines.next_if(|(_, line)| !line.is_empty()) {
+            block.push(line);
+        }
+        Some((first, block.join("\n")))
+    })
+}
+
+pub fn read_sections(file_name: &str) -> Vec<String> {
+    sections(&read_string(file_name)).collect()
+}
+
+/**
+ * Parses every line, reporting errors at their line in the whole input
+ */
+pub fn parse_lines<T, S: AsRef<str>>(
+    lines: &[S],
+    parse: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| parse(line.as_ref()).map_err(|e| e.offset_lines(index)))
+        .collect()
+}
+
+/**
+ * All signed integers in the text, in order. A '-' directly in front of a digit is always read as
+ * a sign, so "1-2" gives [1, -2]; use uints when dashes separate ranges.
+ * Panics when a number does not fit in T.
+ */
+pub fn ints<T>(text: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    numbers(text, true)
+        .map(|(_, number)| parse_number(number))
+        .collect()
+}
+
+/**
+ * All runs of digits in the text, in order, ignoring any signs.
+ * Panics when a number does not fit in T.
+ */
+pub fn uints<T>(text: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    numbers(text, false)
+        .map(|(_, number)| parse_number(number))
+        .collect()
+}
+
+/**
+ * The signed integers in the text when there are exactly N of them, all fitting in T.
+ * Returns None otherwise, which includes negative numbers for an unsigned T.
+ */
+pub fn ints_n<T: FromStr, const N: usize>(text: &str) -> Option<[T; N]> {
+    numbers(text, true)
+        .map(|(offset, number)| try_parse_number(text, offset, number).ok())
+        .collect::<Option<Vec<T>>>()?
+        .try_into()
+        .ok()
+}
+
+/**
+ * Like ints, but a number that does not fit in T is a ParseError rather than a panic
+ */
+pub fn try_ints<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    numbers(text, true)
+        .map(|(offset, number)| try_parse_number(text, offset, number))
+        .collect()
+}
+
+/**
+ * Like uints, but a number that does not fit in T is a ParseError rather than a panic
+ */
+pub fn try_uints<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    numbers(text, false)
+        .map(|(offset, number)| try_parse_number(text, offset, number))
+        .collect()
+}
+
+/**
+ * The byte offset and text of every number
+ */
+fn numbers(text: &str, signed: bool) -> impl Iterator<Item = (usize, &str)> {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+
+    std::iter::from_fn(move || {
+        while index < bytes.len() {
+            let start = index;
+            if signed && bytes[index] == b'-' {
+                index += 1;
+            }
+            let digits = index;
+            while index < bytes.len() && bytes[index].is_ascii_digit() {
+                index += 1;
+            }
+            if index > digits {
+                return Some((start, &text[start..index]));
+            }
+            index = start + 1;
+        }
+        None
+    })
+}
+
+fn parse_number<T>(number: &str) -> T
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    number
+        .parse()
+        .unwrap_or_else(|e| panic!("cannot parse {} as a number: {:?}", number, e))
+}
+
+/**
+ * Parses a number found by numbers at the given offset in the text
+ */
+fn try_parse_number<T: FromStr>(text: &str, offset: usize, number: &str) -> Result<T, ParseError> {
+    number.parse().map_err(|_| {
+        Input::at(text, offset).error(format!("a number fitting in {}", short_type_name::<T>()))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,6 +189,71 @@ mod tests {
     #[test]
     fn reading_a_file_returns_its_content() {
         let content = read("src/lib.rs");
-        assert!(content.contains(&"use std::{fs::File, io::Read};".to_owned()))
+        assert!(content.contains(&"pub mod string;".to_owned()))
+    }
+
+    #[test]
+    fn sections_test() {
+        let expected = vec!["seeds: 1 2".to_owned(), "a:\n3 4\n5 6".to_owned()];
+
+        assert_eq!(
+            expected,
+            sections("seeds: 1 2\n\na:\n3 4\n5 6").collect::<Vec<_>>()
+        );
+        assert_eq!(
+            expected,
+            sections("seeds: 1 2\r\n\r\na:\r\n3 4\r\n5 6\r\n").collect::<Vec<_>>()
+        );
+        assert_eq!(
+            expected,
+            sections("\n\nseeds: 1 2  \n \t\n\n\na: \n3 4\n5 6\n\n\n").collect::<Vec<_>>()
+        );
+        assert_eq!(None, sections(" \n\r\n").next());
+        assert_eq!(
+            vec![(1, "a".to_owned()), (4, "b\nc".to_owned())],
+            numbered_sections("\na\n  \n\nb\r\nc\n").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn parse_lines_test() {
+        let lines = ["1 2", "3", "4 999"];
+        assert_eq!(
+            Ok(vec![vec![1, 2], vec![3]]),
+            parse_lines(&lines[..2], try_uints::<u8>)
+        );
+        assert_eq!(
+            Err(ParseError::new(3, 3, "4 999", "a number fitting in u8")),
+            parse_lines(&lines, try_uints::<u8>)
+        );
+        assert_eq!(Ok(vec![-4, 2]), try_ints::<i8>("x=-4, y=2"));
+    }
+
+    #[test]
+    fn ints_test() {
+        assert_eq!(vec![7, -3, 12, 0], ints::<i32>("p=7,-3 v=+12 and 0 apples"));
+        assert_eq!(vec![1, -2], ints::<i64>("1-2"));
+        assert_eq!(vec![1, 2], uints::<u8>("1-2"));
+        assert_eq!(
+            vec![12, 41, 48, 83, 86],
+            uints::<u32>("Card  12: 41 48 | 83 86")
+        );
+        assert_eq!(Vec::<i32>::new(), ints::<i32>("no - numbers here"));
+    }
+
+    #[test]
+    fn ints_n_test() {
+        assert_eq!(Some([50, 98, 2]), ints_n::<usize, 3>("50 98 2"));
+        assert_eq!(None, ints_n::<usize, 3>("50 98"));
+        assert_eq!(None, ints_n::<usize, 3>("50 98 2 1"));
+        assert_eq!(None, ints_n::<usize, 3>("50 -98 2"));
+        assert_eq!(None, ints_n::<u8, 3>("50 980 2"));
+        assert_eq!(Some([-50i8, 98, 2]), ints_n("-50 98 2"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn ints_overflow_test() {
+        ints::<u8>("256");
     }
 }